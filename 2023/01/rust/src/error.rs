@@ -1,7 +1,16 @@
+use aoc_helpers::context::{Locate, Location};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
-pub enum Error<'a> {
-    #[error("no digits found in line {0:?}")]
-    NoDigitsInLine(&'a str),
+pub enum Error {
+    #[error("no digits found on line {line}")]
+    NoDigitsInLine { line: usize },
+}
+
+impl Locate for Error {
+    fn location(&self) -> Option<Location> {
+        match self {
+            Error::NoDigitsInLine { line } => Some(Location::line(*line)),
+        }
+    }
 }
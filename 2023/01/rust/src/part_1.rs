@@ -1,31 +1,35 @@
 use crate::error::Error;
 use crate::shared::LineDigits;
+use aoc_helpers::parse::Span;
 
-pub fn run(input: &str) -> Result<String, Error> {
+pub fn run(lines: &[Span]) -> Result<String, Error> {
     let mut sum = 0;
 
-    for line in input.lines() {
+    for &line in lines {
         sum += number_for_line(line)?;
     }
 
     Ok(sum.to_string())
 }
 
-fn number_for_line(line: &str) -> Result<u32, Error> {
+fn number_for_line(line: Span) -> Result<u32, Error> {
     let mut digits = LineDigits::new();
 
-    for c in line.chars() {
+    for c in line.text.chars() {
         if let Some(digit) = c.to_digit(10) {
             digits.add_digit(digit);
         }
     }
 
-    digits.get_number().ok_or(Error::NoDigitsInLine(line))
+    digits
+        .get_number()
+        .ok_or(Error::NoDigitsInLine { line: line.line })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -35,7 +39,7 @@ mod tests {
         treb7uchet
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|lines| run(&lines));
 
         assert_eq!(output, Ok("142".to_owned()));
     }
@@ -48,7 +52,7 @@ mod tests {
                 $(
                     #[test]
                     fn $name() {
-                        let output = number_for_line($input);
+                        let output = number_for_line(Span::new($input));
 
                         assert_eq!(output, $expected);
                     }
@@ -63,6 +67,6 @@ mod tests {
         example_line_3: "a1b2c3d4e5f" => Ok(15),
         example_line_4: "treb7uchet" => Ok(77),
 
-        no_numbers: "nonumbers" => Err(Error::NoDigitsInLine("nonumbers")),
+        no_numbers: "nonumbers" => Err(Error::NoDigitsInLine { line: 1 }),
     );
 }
@@ -1,26 +1,32 @@
 use crate::error::Error;
 use crate::shared::LineDigits;
+use aoc_helpers::parse::Span;
 
-pub fn run(input: &str) -> Result<String, Error> {
+pub fn run(lines: &[Span]) -> Result<String, Error> {
     let mut sum = 0;
 
-    for line in input.lines() {
+    for &line in lines {
         sum += number_for_line(line)?;
     }
 
     Ok(sum.to_string())
 }
 
-fn number_for_line(line: &str) -> Result<u32, Error> {
+fn number_for_line(line: Span) -> Result<u32, Error> {
     let mut digits = LineDigits::new();
 
-    for (line_idx, c) in line.char_indices() {
-        if let Some(digit) = c.to_digit(10).or_else(|| digit_from_str(&line[line_idx..])) {
+    for (line_idx, c) in line.text.char_indices() {
+        if let Some(digit) = c
+            .to_digit(10)
+            .or_else(|| digit_from_str(&line.text[line_idx..]))
+        {
             digits.add_digit(digit);
         }
     }
 
-    digits.get_number().ok_or(Error::NoDigitsInLine(line))
+    digits
+        .get_number()
+        .ok_or(Error::NoDigitsInLine { line: line.line })
 }
 
 const NUMBER_WORDS: [&str; 9] = [
@@ -42,6 +48,7 @@ fn digit_from_str(partial_line: &str) -> Option<u32> {
 mod tests {
     use super::*;
     pub use crate::error::Error;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -54,7 +61,7 @@ mod tests {
         7pqrstsixteen
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|lines| run(&lines));
 
         assert_eq!(output, Ok("281".to_owned()));
     }
@@ -67,7 +74,7 @@ mod tests {
                 $(
                     #[test]
                     fn $name() {
-                        let output = number_for_line($input);
+                        let output = number_for_line(Span::new($input));
 
                         assert_eq!(output, $expected);
                     }
@@ -96,7 +103,7 @@ mod tests {
         overlap_twoneight: "twoneight" => Ok(28),
         overlap_eightwoneight: "eightwoneight" => Ok(88),
 
-        no_numbers: "nonumbers" => Err(Error::NoDigitsInLine("nonumbers")),
+        no_numbers: "nonumbers" => Err(Error::NoDigitsInLine { line: 1 }),
     );
 
     macro_rules! digit_from_str_test {
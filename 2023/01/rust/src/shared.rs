@@ -1,3 +1,13 @@
+use aoc_helpers::parse::{self, Span};
+
+use crate::error::Error;
+
+/// Each part reads digits from the lines differently, so they just get split
+#[allow(clippy::unnecessary_wraps)]
+pub fn parse(input: &str) -> Result<Vec<Span<'_>>, Error> {
+    Ok(parse::lines(input).collect())
+}
+
 pub struct LineDigits(Option<(u32, u32)>);
 
 impl LineDigits {
@@ -1,16 +1,13 @@
+use aoc_helpers::context::Locate;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
-pub enum Error<'a> {
-    #[error("no game header found in line {0:?}")]
-    NoGameHeaderInLine(&'a str),
-
-    #[error("invalid game header {0:?}")]
-    InvalidGameHeader(&'a str),
-
-    #[error("invalid cube set {0:?}")]
-    InvalidCubeSet(&'a str),
-
+pub enum Error {
     #[error("unknown color `{0}`")]
-    UnknownColor(&'a str),
+    UnknownColor(String),
+
+    #[error("{0}")]
+    AocHelper(#[from] aoc_helpers::Error),
 }
+
+impl Locate for Error {}
@@ -1,12 +1,11 @@
 use crate::error::Error;
 use crate::shared::GameStats;
 
-pub fn run(input: &str) -> Result<String, Error> {
+#[allow(clippy::unnecessary_wraps)]
+pub fn run(games: &[GameStats]) -> Result<String, Error> {
     let mut sum = 0;
 
-    for line in input.lines() {
-        let stats = GameStats::parse_line(line)?;
-
+    for stats in games {
         sum += stats.max_red * stats.max_green * stats.max_blue;
     }
 
@@ -16,6 +15,7 @@ pub fn run(input: &str) -> Result<String, Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -26,7 +26,7 @@ Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|games| run(&games));
 
         assert_eq!(output, Ok("2286".to_owned()));
     }
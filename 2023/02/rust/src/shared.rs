@@ -1,6 +1,14 @@
 use crate::error::Error;
+use aoc_helpers::{
+    parse::{self, Span},
+    scan,
+};
 use std::cmp::max;
 
+pub fn parse(input: &str) -> Result<Vec<GameStats>, Error> {
+    parse::lines(input).map(GameStats::parse_line).collect()
+}
+
 #[derive(PartialEq, Eq, Debug)]
 pub struct GameStats {
     pub id: i32,
@@ -11,38 +19,21 @@ pub struct GameStats {
 }
 
 impl GameStats {
-    pub fn parse_line(line: &str) -> Result<GameStats, Error> {
-        let (header, game) = line
-            .split_once(':')
-            .ok_or(Error::NoGameHeaderInLine(line))?;
-
-        let game = game.trim();
-
-        let game_id = header
-            .split_once(' ')
-            .ok_or(Error::InvalidGameHeader(header))?
-            .1
-            .parse::<i32>()
-            .map_err(|_| Error::InvalidGameHeader(header))?;
+    pub fn parse_line(line: Span) -> Result<GameStats, Error> {
+        let (game_id, game) = scan!(line, "Game {id}: {game}", i32, Span)?;
 
         let mut max_red = 0;
         let mut max_green = 0;
         let mut max_blue = 0;
 
-        for cube_set in game.split([';', ',']) {
-            let (count, color) = cube_set
-                .trim()
-                .split_once(' ')
-                .ok_or(Error::InvalidCubeSet(cube_set))?;
-            let count = count
-                .parse::<i32>()
-                .map_err(|_| Error::InvalidCubeSet(cube_set))?;
+        for cube_set in game.split(&[';', ',']) {
+            let (count, color) = scan!(cube_set.trim(), "{count} {color}", i32, &str)?;
 
             match color {
                 "red" => max_red = max(max_red, count),
                 "green" => max_green = max(max_green, count),
                 "blue" => max_blue = max(max_blue, count),
-                _ => return Err(Error::UnknownColor(color)),
+                _ => return Err(Error::UnknownColor(color.to_owned())),
             }
         }
 
@@ -59,6 +50,11 @@ impl GameStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_helpers::ParseErrorKind;
+
+    fn parse_error(line: usize, column: usize, kind: ParseErrorKind) -> Error {
+        Error::AocHelper(aoc_helpers::Error::Parse { line, column, kind })
+    }
 
     macro_rules! line_test {
         ($suite:ident, $($name:ident: $input:expr => $expected:expr,)*) => {
@@ -68,7 +64,7 @@ mod tests {
                 $(
                     #[test]
                     fn $name() {
-                        let stats = GameStats::parse_line($input);
+                        let stats = GameStats::parse_line(Span::new($input));
 
                         assert_eq!(stats, $expected);
                     }
@@ -119,11 +115,17 @@ mod tests {
             }
         ),
 
-        err_no_colon: "Game 1 4 red" => Err(Error::NoGameHeaderInLine("Game 1 4 red")),
-        err_no_space_in_header: "Game1: 4 red" => Err(Error::InvalidGameHeader("Game1")),
-        err_non_numeric_game_id: "Game one: 4 red" => Err(Error::InvalidGameHeader("Game one")),
-        err_no_space_in_cube_set: "Game 1: 4red" => Err(Error::InvalidCubeSet("4red")),
-        err_non_numeric_cube_set: "Game 1: four red" => Err(Error::InvalidCubeSet("four red")),
-        err_unknown_color: "Game 1: 4 pink" => Err(Error::UnknownColor("pink")),
+        err_no_colon: "Game 1 4 red" => Err(parse_error(1, 13, ParseErrorKind::Expected(": ".to_owned()))),
+        err_no_space_in_header: "Game1: 4 red" => Err(parse_error(1, 1, ParseErrorKind::Expected("Game ".to_owned()))),
+        err_non_numeric_game_id: "Game one: 4 red" => Err(parse_error(1, 6, ParseErrorKind::InvalidValue {
+            text: "one".to_owned(),
+            reason: "invalid digit found in string".to_owned(),
+        })),
+        err_no_space_in_cube_set: "Game 1: 4red" => Err(parse_error(1, 13, ParseErrorKind::Expected(" ".to_owned()))),
+        err_non_numeric_cube_set: "Game 1: four red" => Err(parse_error(1, 9, ParseErrorKind::InvalidValue {
+            text: "four".to_owned(),
+            reason: "invalid digit found in string".to_owned(),
+        })),
+        err_unknown_color: "Game 1: 4 pink" => Err(Error::UnknownColor("pink".to_owned())),
     );
 }
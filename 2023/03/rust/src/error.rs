@@ -1,5 +1,6 @@
 use std::num::ParseIntError;
 
+use aoc_helpers::context::Locate;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -9,3 +10,5 @@ pub enum Error {
     #[error("failed parsing number: {0}")]
     FailedNumberParsing(#[from] ParseIntError),
 }
+
+impl Locate for Error {}
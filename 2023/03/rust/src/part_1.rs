@@ -1,12 +1,11 @@
 use crate::error::Error;
 use crate::shared::EngineSchematic;
+use aoc_helpers::hash::FastHashSet;
 use aoc_helpers::neighbors::Grid2D;
-use std::collections::HashSet;
 
-pub fn run(input: &str) -> Result<String, Error> {
-    let schematic = EngineSchematic::parse(input)?;
-
-    let mut number_ids = HashSet::new();
+#[allow(clippy::unnecessary_wraps)]
+pub fn run(schematic: &EngineSchematic) -> Result<String, Error> {
+    let mut number_ids = FastHashSet::default();
 
     for (x, y, byte) in schematic.iter() {
         match byte {
@@ -48,7 +47,7 @@ mod tests {
 .664.598..
 ";
 
-        let output = run(input);
+        let output = EngineSchematic::parse(input).and_then(|schematic| run(&schematic));
 
         assert_eq!(output, Ok("4361".to_owned()));
     }
@@ -1,13 +1,12 @@
 use crate::error::Error;
 use crate::shared::EngineSchematic;
+use aoc_helpers::hash::{FastHashSet, FxBuildHasher};
 use aoc_helpers::neighbors::Grid2D;
-use std::collections::HashSet;
-
-pub fn run(input: &str) -> Result<String, Error> {
-    let schematic = EngineSchematic::parse(input)?;
 
+#[allow(clippy::unnecessary_wraps)]
+pub fn run(schematic: &EngineSchematic) -> Result<String, Error> {
     let mut sum = 0;
-    let mut gears = HashSet::with_capacity(8); // Reused to avoid excess heap allocations
+    let mut gears = FastHashSet::with_capacity_and_hasher(8, FxBuildHasher::default()); // Reused to avoid excess heap allocations
 
     for (x, y, &byte) in schematic.iter() {
         if byte == b'*' {
@@ -49,7 +48,7 @@ mod tests {
 .664.598..
 ";
 
-        let output = run(input);
+        let output = EngineSchematic::parse(input).and_then(|schematic| run(&schematic));
 
         assert_eq!(output, Ok("467835".to_owned()));
     }
@@ -1,10 +1,11 @@
 use crate::{error::Error, shared::Card};
 
-pub fn run(input: &str) -> Result<String, Error> {
+#[allow(clippy::unnecessary_wraps)]
+pub fn run(cards: &[Card]) -> Result<String, Error> {
     let mut wins = 0;
 
-    for line in input.lines() {
-        let card_wins = Card::parse(line)?.compute_wins();
+    for card in cards {
+        let card_wins = card.compute_wins();
 
         if card_wins > 0 {
             wins += 1 << (card_wins - 1);
@@ -17,6 +18,7 @@ pub fn run(input: &str) -> Result<String, Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -28,7 +30,7 @@ Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|cards| run(&cards));
 
         assert_eq!(output, Ok("13".to_owned()));
     }
@@ -1,20 +1,21 @@
 use crate::{error::Error, shared::Card};
 
-pub fn run(input: &str) -> Result<String, Error> {
-    let mut cards = 0;
+#[allow(clippy::unnecessary_wraps)]
+pub fn run(cards: &[Card]) -> Result<String, Error> {
+    let mut total = 0;
     let mut tracker = CopiesTracker::new();
 
-    for line in input.lines() {
-        let card_wins = Card::parse(line)?.compute_wins();
+    for card in cards {
+        let card_wins = card.compute_wins();
         let copies = tracker.get_stored_copies() + 1;
 
         tracker.next_card();
         tracker.add_card(card_wins, copies);
 
-        cards += copies;
+        total += copies;
     }
 
-    Ok(cards.to_string())
+    Ok(total.to_string())
 }
 
 #[derive(Debug)]
@@ -59,6 +60,7 @@ impl CopiesTracker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -70,7 +72,7 @@ Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|cards| run(&cards));
 
         assert_eq!(output, Ok("30".to_owned()));
     }
@@ -1,29 +1,28 @@
-use std::{collections::HashSet, num::ParseIntError};
+use aoc_helpers::{
+    hash::FastHashSet,
+    parse::{self, Span},
+    scan,
+};
 
 use crate::error::Error;
 
+pub fn parse(input: &str) -> Result<Vec<Card>, Error> {
+    parse::lines(input).map(Card::parse).collect()
+}
+
 pub struct Card {
-    winning_numbers: HashSet<u32>,
-    card_numbers: HashSet<u32>,
+    winning_numbers: FastHashSet<u32>,
+    card_numbers: FastHashSet<u32>,
 }
 
 impl Card {
-    pub fn parse(line: &str) -> Result<Card, Error> {
-        let card = line
-            .split_once(':')
-            .ok_or(Error::NoCardHeaderInLine(line))?
-            .1
-            .trim();
-
-        let (winning_numbers, card_numbers) =
-            card.split_once('|').ok_or(Error::NoCardSeparator(card))?;
-
-        let winning_numbers = parse_numbers(winning_numbers).collect::<Result<HashSet<_>, _>>()?;
-        let card_numbers = parse_numbers(card_numbers).collect::<Result<HashSet<_>, _>>()?;
+    pub fn parse(line: Span) -> Result<Card, Error> {
+        let (_, winning_numbers, card_numbers): (u32, Vec<u32>, Vec<u32>) =
+            scan!(line, "Card {id}: {winning} | {numbers}")?;
 
         Ok(Card {
-            winning_numbers,
-            card_numbers,
+            winning_numbers: winning_numbers.into_iter().collect(),
+            card_numbers: card_numbers.into_iter().collect(),
         })
     }
 
@@ -33,11 +32,3 @@ impl Card {
             .count()
     }
 }
-
-fn parse_numbers(input: &str) -> impl Iterator<Item = Result<u32, ParseIntError>> + '_ {
-    input
-        .trim()
-        .split(' ')
-        .filter(|txt| !txt.is_empty())
-        .map(str::parse)
-}
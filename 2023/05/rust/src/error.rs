@@ -1,7 +1,22 @@
+use aoc_helpers::context::Locate;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum Error {
     #[error("no smallest number found")]
     NoSmallestFound,
+
+    #[error("unknown map type {0:?}")]
+    UnknownMapType(String),
+
+    #[error("invalid section header {0:?}")]
+    InvalidHeader(String),
+
+    #[error("mapping {0:?} doesn't have exactly 3 numbers")]
+    InvalidMapping(String),
+
+    #[error("{0}")]
+    AocHelper(#[from] aoc_helpers::Error),
 }
+
+impl Locate for Error {}
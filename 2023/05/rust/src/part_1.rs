@@ -1,41 +1,15 @@
-use crate::{
-    error::Error,
-    shared::{Almanac, Mapping, Mappings},
-};
+use crate::{error::Error, shared::Almanac};
 
-pub fn run(input: &str) -> Result<String, Error> {
-    let mut almanac = Almanac::parse(input);
+pub fn run(almanac: &Almanac) -> Result<String, Error> {
+    let seed_to_location = almanac.seed_to_location();
 
-    // Convert every seed into a 1-sized range
-    let seed_ranges = almanac
+    almanac
         .seeds
         .iter()
-        .map(|&i| i..i + 1)
-        .map(|r| Mapping {
-            source: r.clone(),
-            dest: r,
-        })
-        .collect::<Vec<_>>();
-
-    // Merge mappings from upstream (source) to downstream (dest) to create one
-    // precomputed seed-to-location mapping
-    let mut combined = Mappings(seed_ranges)
-        .merge(&mut almanac.seed_to_soil)
-        .merge(&mut almanac.soil_to_fertilizer)
-        .merge(&mut almanac.fertilizer_to_water)
-        .merge(&mut almanac.water_to_light)
-        .merge(&mut almanac.light_to_temperature)
-        .merge(&mut almanac.temperature_to_humidity)
-        .merge(&mut almanac.humidity_to_location);
-
-    // Sort the mappings by dest so we can start with the smallest mapped dests
-    combined.sort_by_dest();
-
-    combined
-        .0
-        .first()
+        .map(|&seed| seed_to_location.apply(seed))
+        .min()
         .ok_or(Error::NoSmallestFound)
-        .map(|m| m.dest.start.to_string())
+        .map(|location| location.to_string())
 }
 
 #[cfg(test)]
@@ -79,7 +53,7 @@ humidity-to-location map:
 56 93 4
 ";
 
-        let output = run(input);
+        let output = Almanac::parse(input).and_then(|almanac| run(&almanac));
 
         assert_eq!(output, Ok("35".to_owned()));
     }
@@ -1,12 +1,18 @@
-use std::num::ParseFloatError;
+use std::num::ParseIntError;
 
+use aoc_helpers::context::Locate;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum Error {
-    #[error("missing line header")]
-    MissingLineHeader,
+    #[error("missing {0:?} line")]
+    MissingLine(&'static str),
 
-    #[error("parse float error: {0}")]
-    F64ParseError(#[from] ParseFloatError),
+    #[error("failed parsing number: {0}")]
+    FailedNumberParsing(#[from] ParseIntError),
+
+    #[error("{0}")]
+    AocHelper(#[from] aoc_helpers::Error),
 }
+
+impl Locate for Error {}
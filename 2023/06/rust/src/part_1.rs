@@ -1,10 +1,11 @@
-use crate::{error::Error, shared::valid_inputs_for_problem};
+use crate::{
+    error::Error,
+    shared::{valid_inputs_for_problem, Sheet},
+};
 
-pub fn run(input: &str) -> Result<String, Error> {
-    let (times, distances) = input.split_once('\n').unwrap();
-
-    let times = parse_line(times)?;
-    let distances = parse_line(distances)?;
+pub fn run(sheet: &Sheet) -> Result<String, Error> {
+    let times = sheet.times.ints::<i64>()?;
+    let distances = sheet.distances.ints::<i64>()?;
 
     let mut options = 1;
 
@@ -15,20 +16,10 @@ pub fn run(input: &str) -> Result<String, Error> {
     Ok(options.to_string())
 }
 
-fn parse_line(line: &str) -> Result<Vec<f64>, Error> {
-    line.split_once(':')
-        .ok_or(Error::MissingLineHeader)?
-        .1
-        .trim()
-        .split(' ')
-        .filter(|s| !s.is_empty())
-        .map(|s| s.parse::<f64>().map_err(Error::from))
-        .collect::<Result<Vec<f64>, Error>>()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -36,7 +27,7 @@ mod tests {
         Distance:  9  40  200
         ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|sheet| run(&sheet));
 
         assert_eq!(output, Ok("288".to_owned()));
     }
@@ -1,29 +1,31 @@
-use crate::{error::Error, shared::valid_inputs_for_problem};
+use aoc_helpers::parse::Span;
 
-pub fn run(input: &str) -> Result<String, Error> {
-    let (times, distances) = input.split_once('\n').unwrap();
+use crate::{
+    error::Error,
+    shared::{valid_inputs_for_problem, Sheet},
+};
 
-    let time = parse_line(times)?;
-    let distance = parse_line(distances)?;
+pub fn run(sheet: &Sheet) -> Result<String, Error> {
+    let time = join_digits(sheet.times)?;
+    let distance = join_digits(sheet.distances)?;
 
     Ok(valid_inputs_for_problem(time, distance).to_string())
 }
 
-fn parse_line(line: &str) -> Result<f64, Error> {
-    line.split_once(':')
-        .ok_or(Error::MissingLineHeader)?
-        .1
-        .trim()
+fn join_digits(numbers: Span) -> Result<i64, Error> {
+    numbers
+        .text
         .chars()
         .filter(|&c| c != ' ')
         .collect::<String>()
-        .parse::<f64>()
+        .parse::<i64>()
         .map_err(Error::from)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -31,7 +33,7 @@ mod tests {
         Distance:  9  40  200
         ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|sheet| run(&sheet));
 
         assert_eq!(output, Ok("71503".to_owned()));
     }
@@ -1,9 +1,34 @@
-#[allow(clippy::cast_possible_truncation)]
-pub fn valid_inputs_for_problem(time: f64, distance: f64) -> i32 {
-    let o = (time * time / 4.0 - distance).sqrt();
+use aoc_helpers::{
+    math::count_quadratic_positive,
+    parse::{self, Span},
+};
 
-    let min = (-o + time / 2.0).floor() as i32 + 1;
-    let max = (o + time / 2.0).ceil() as i32;
+use crate::error::Error;
 
-    max - min
+/// The race sheet's lines after their headers, which part 1 reads as separate
+/// races and part 2 as one race with the spaces taken out
+pub struct Sheet<'a> {
+    pub times: Span<'a>,
+    pub distances: Span<'a>,
+}
+
+pub fn parse(input: &str) -> Result<Sheet<'_>, Error> {
+    let mut lines = parse::lines(input);
+
+    Ok(Sheet {
+        times: header_value(lines.next(), "Time:")?,
+        distances: header_value(lines.next(), "Distance:")?,
+    })
+}
+
+fn header_value<'a>(line: Option<Span<'a>>, header: &'static str) -> Result<Span<'a>, Error> {
+    line.ok_or(Error::MissingLine(header))?
+        .header_value(header)
+        .map_err(Error::from)
+}
+
+/// Holding the button for `x` ms travels `x * (time - x)` mm, so this counts the
+/// `x` in `0..=time` where `-x² + time·x - distance > 0`
+pub fn valid_inputs_for_problem(time: i64, distance: i64) -> u64 {
+    count_quadratic_positive(-1, time, -distance, 0..time + 1)
 }
@@ -1,12 +1,10 @@
-use std::num::ParseIntError;
-
+use aoc_helpers::context::Locate;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum Error {
-    #[error("invalid hand")]
-    InvalidHand,
-
-    #[error("failed parsing number: {0}")]
-    FailedNumberParsing(#[from] ParseIntError),
+    #[error("{0}")]
+    AocHelper(#[from] aoc_helpers::Error),
 }
+
+impl Locate for Error {}
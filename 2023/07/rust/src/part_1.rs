@@ -1,14 +1,11 @@
 use crate::{error::Error, shared::Hand};
 
-pub fn run(input: &str) -> Result<String, Error> {
-    let mut hands: Vec<(Hand, u32)> = input
-        .lines()
-        .map(|line| {
-            let (hand, bid) = line.split_once(' ').ok_or(Error::InvalidHand)?;
-
-            Ok((Hand::parse(hand, false), bid.parse::<u32>()?))
-        })
-        .collect::<Result<Vec<(Hand<'_>, u32)>, Error>>()?;
+#[allow(clippy::unnecessary_wraps)]
+pub fn run(hands: &[(&str, u32)]) -> Result<String, Error> {
+    let mut hands = hands
+        .iter()
+        .map(|&(cards, bid)| (Hand::parse(cards, false), bid))
+        .collect::<Vec<_>>();
 
     hands.sort_unstable_by_key(|h| h.0.clone());
 
@@ -24,6 +21,7 @@ pub fn run(input: &str) -> Result<String, Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -34,7 +32,7 @@ KTJJT 220
 QQQJA 483
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|hands| run(&hands));
 
         assert_eq!(output, Ok("6440".to_owned()));
     }
@@ -1,4 +1,20 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::cmp::Ordering;
+
+use aoc_helpers::{hash::FastHashMap, parse};
+
+use crate::error::Error;
+
+/// Each hand's cards and bid. What the cards are worth depends on the part,
+/// since part 2 reads `J` as a joker.
+pub fn parse(input: &str) -> Result<Vec<(&str, u32)>, Error> {
+    parse::lines(input)
+        .map(|line| {
+            let (cards, bid) = line.split_once(" ")?;
+
+            Ok((cards.text, bid.parse::<u32>()?))
+        })
+        .collect()
+}
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
 pub enum HandType {
@@ -64,8 +80,8 @@ pub struct Hand<'a> {
 }
 
 impl<'a> Hand<'a> {
-    pub fn parse(s: &str, j_is_joker: bool) -> Hand {
-        let mut chars: HashMap<char, u32> = HashMap::new();
+    pub fn parse(s: &'a str, j_is_joker: bool) -> Hand<'a> {
+        let mut chars: FastHashMap<char, u32> = FastHashMap::default();
 
         for ch in s.chars() {
             chars.entry(ch).and_modify(|n| *n += 1).or_insert(1);
@@ -112,7 +128,7 @@ impl<'a> Hand<'a> {
     }
 }
 
-impl<'a> std::cmp::Ord for Hand<'a> {
+impl std::cmp::Ord for Hand<'_> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.play.cmp(&other.play).then_with(|| {
             for (s, o) in self.cards.chars().zip(other.cards.chars()) {
@@ -128,7 +144,7 @@ impl<'a> std::cmp::Ord for Hand<'a> {
     }
 }
 
-impl<'a> std::cmp::PartialOrd for Hand<'a> {
+impl std::cmp::PartialOrd for Hand<'_> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
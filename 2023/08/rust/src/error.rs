@@ -1,7 +1,22 @@
+use aoc_helpers::context::Locate;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum Error {
     #[error("invalid map format")]
     InvalidMapFormat,
+
+    #[error("no starting nodes ending in A")]
+    NoGhosts,
+
+    #[error("ghosts never all finish at the same time")]
+    GhostsNeverMeet,
+
+    #[error("ghosts finish at too many points in their cycles to combine")]
+    TooManyFinishes,
+
+    #[error("{0}")]
+    AocHelper(#[from] aoc_helpers::Error),
 }
+
+impl Locate for Error {}
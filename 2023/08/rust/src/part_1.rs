@@ -1,7 +1,7 @@
-use crate::{error::Error, shared::parse_camel_map};
+use crate::{error::Error, shared::CamelMap};
 
-pub fn run(input: &str) -> Result<String, Error> {
-    let (steps, nodes) = parse_camel_map(input)?;
+pub fn run(map: &CamelMap) -> Result<String, Error> {
+    let CamelMap { steps, nodes } = map;
 
     let mut steps_taken = 0;
     let mut node = "AAA";
@@ -25,6 +25,7 @@ pub fn run(input: &str) -> Result<String, Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse_camel_map;
 
     #[test]
     fn example_1() {
@@ -39,7 +40,7 @@ GGG = (GGG, GGG)
 ZZZ = (ZZZ, ZZZ)
 ";
 
-        let output = run(input);
+        let output = parse_camel_map(input).and_then(|map| run(&map));
 
         assert_eq!(output, Ok("2".to_owned()));
     }
@@ -53,7 +54,7 @@ BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)
 ";
 
-        let output = run(input);
+        let output = parse_camel_map(input).and_then(|map| run(&map));
 
         assert_eq!(output, Ok("6".to_owned()));
     }
@@ -1,37 +1,129 @@
-use num::Integer;
+use aoc_helpers::{
+    cycle::{find_cycle_hashed, Cycle},
+    hash::FastHashMap,
+    math::crt,
+};
 
-use crate::{error::Error, shared::parse_camel_map};
+use crate::{
+    error::Error,
+    shared::{CamelMap, Node},
+};
 
-pub fn run(input: &str) -> Result<String, Error> {
-    let (steps, nodes) = parse_camel_map(input)?;
+pub fn run(map: &CamelMap) -> Result<String, Error> {
+    let CamelMap { steps, nodes } = map;
 
-    let mut result: u64 = 1;
+    if steps.iter().any(|&step| step != 'L' && step != 'R') {
+        return Err(Error::InvalidMapFormat);
+    }
+
+    let ghosts = nodes
+        .keys()
+        .copied()
+        .filter(|n| n.ends_with('A'))
+        .map(|start| GhostPath::walk(start, steps, nodes))
+        .collect::<Vec<_>>();
+
+    if ghosts.is_empty() {
+        return Err(Error::NoGhosts);
+    }
+
+    first_meeting(&ghosts).map(|steps_taken| steps_taken.to_string())
+}
 
-    for mut node in nodes.keys().copied().filter(|n| n.ends_with('A')) {
-        let mut steps_taken = 0;
+/// How many combinations of finish offsets `first_meeting` will try. Puzzle
+/// inputs have one finish per cycle, so this only guards against inputs
+/// built to blow up.
+const MAX_COMBINATIONS: usize = 1 << 16;
 
-        for step in steps.iter().copied().cycle() {
-            node = match step {
+/// The steps where a ghost is on a `Z` node, which start repeating once the
+/// ghost is back on the same node at the same point in the instructions
+struct GhostPath {
+    cycle: Cycle,
+    finishes: Vec<usize>,
+}
+
+impl GhostPath {
+    fn walk(start: &str, steps: &[char], nodes: &FastHashMap<&str, Node>) -> GhostPath {
+        let (cycle, history) = find_cycle_hashed((start, 0), |&(node, i)| {
+            let next = match steps[i] {
                 'L' => nodes[node].left,
-                'R' => nodes[node].right,
-                _ => return Err(Error::InvalidMapFormat),
+                _ => nodes[node].right,
             };
-            steps_taken += 1;
 
-            if node.ends_with('Z') {
-                break;
-            }
+            (next, (i + 1) % steps.len())
+        });
+
+        let finishes = history
+            .iter()
+            .enumerate()
+            .filter(|(_, (node, _))| node.ends_with('Z'))
+            .map(|(step, _)| step)
+            .collect();
+
+        GhostPath { cycle, finishes }
+    }
+
+    fn finished_at(&self, step: usize) -> bool {
+        self.finishes
+            .binary_search(&self.cycle.equivalent_step(step))
+            .is_ok()
+    }
+}
+
+fn first_meeting(ghosts: &[GhostPath]) -> Result<usize, Error> {
+    // Until every ghost has reached its cycle, check each step directly
+    let prefix = ghosts.iter().map(|g| g.cycle.start).max().unwrap_or(0);
+
+    if let Some(step) = (0..prefix).find(|&step| ghosts.iter().all(|g| g.finished_at(step))) {
+        return Ok(step);
+    }
+
+    // After that, each ghost finishes at fixed offsets in its cycle, so combine
+    // every choice of offsets with the Chinese Remainder Theorem
+    let mut solutions = vec![(0, 1)];
+
+    for ghost in ghosts {
+        let length = i64::try_from(ghost.cycle.length).map_err(|_| Error::GhostsNeverMeet)?;
+        let offsets = ghost
+            .finishes
+            .iter()
+            .filter(|&&step| step >= ghost.cycle.start)
+            .map(|&step| i64::try_from(step).map_err(|_| Error::GhostsNeverMeet))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if solutions.len() * offsets.len() > MAX_COMBINATIONS {
+            return Err(Error::TooManyFinishes);
         }
 
-        result = result.lcm(&steps_taken);
+        solutions = solutions
+            .iter()
+            .flat_map(|&solution| {
+                offsets
+                    .iter()
+                    .filter_map(move |&offset| crt([solution, (offset, length)]))
+            })
+            .collect();
     }
 
-    Ok(result.to_string())
+    solutions
+        .into_iter()
+        .filter_map(|(step, modulus)| {
+            let (step, modulus) = (usize::try_from(step).ok()?, usize::try_from(modulus).ok()?);
+
+            Some(if step < prefix {
+                step + (prefix - step).div_ceil(modulus) * modulus
+            } else {
+                step
+            })
+        })
+        .min()
+        .ok_or(Error::GhostsNeverMeet)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse_camel_map;
 
     #[test]
     fn example() {
@@ -47,8 +139,50 @@ mod tests {
 XXX = (XXX, XXX)
 ";
 
-        let output = run(input);
+        let output = parse_camel_map(input).and_then(|map| run(&map));
 
         assert_eq!(output, Ok("6".to_owned()));
     }
+
+    #[test]
+    fn offset_cycles() {
+        // 11A finishes after 3, 5, 7, ... steps and 22A after 2, 5, 8, ...
+        // steps, so the lcm of their first finishes would be wrong
+        let input = r"L
+
+11A = (11B, XXX)
+11B = (11C, XXX)
+11C = (11Z, XXX)
+11Z = (11D, XXX)
+11D = (11Z, XXX)
+22A = (22B, XXX)
+22B = (22Z, XXX)
+22Z = (22C, XXX)
+22C = (22D, XXX)
+22D = (22Z, XXX)
+XXX = (XXX, XXX)
+";
+
+        let output = parse_camel_map(input).and_then(|map| run(&map));
+
+        assert_eq!(output, Ok("5".to_owned()));
+    }
+
+    #[test]
+    fn no_ghosts() {
+        let input = "L\n\nAAB = (AAB, AAB)\n";
+
+        let output = parse_camel_map(input).and_then(|map| run(&map));
+
+        assert_eq!(output, Err(Error::NoGhosts));
+    }
+
+    #[test]
+    fn dangling_node() {
+        let input = "L\n\n11A = (11B, 11B)\n";
+
+        let output = parse_camel_map(input).and_then(|map| run(&map));
+
+        assert_eq!(output, Err(Error::InvalidMapFormat));
+    }
 }
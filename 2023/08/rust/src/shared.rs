@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use aoc_helpers::{hash::FastHashMap, parse, scan};
 
 use crate::error::Error;
 
@@ -7,22 +7,34 @@ pub struct Node<'a> {
     pub right: &'a str,
 }
 
-pub fn parse_camel_map(map: &str) -> Result<(Vec<char>, HashMap<&str, Node>), Error> {
-    let (steps_text, nodes_text) = map.split_once("\n\n").ok_or(Error::InvalidMapFormat)?;
+pub struct CamelMap<'a> {
+    pub steps: Vec<char>,
+    pub nodes: FastHashMap<&'a str, Node<'a>>,
+}
+
+pub fn parse_camel_map(map: &str) -> Result<CamelMap<'_>, Error> {
+    let mut sections = parse::sections(map);
+    let (Some(steps_text), Some(nodes_text)) = (sections.next(), sections.next()) else {
+        return Err(Error::InvalidMapFormat);
+    };
 
-    let steps = steps_text.trim().chars().collect::<Vec<_>>();
+    let steps = steps_text.trim().text.chars().collect::<Vec<_>>();
 
-    let mut nodes: HashMap<&str, Node> = HashMap::new();
+    let mut nodes: FastHashMap<&str, Node> = FastHashMap::default();
 
     for line in nodes_text.lines() {
-        let (key, values) = line.split_once(" = ").ok_or(Error::InvalidMapFormat)?;
-        let (left, right) = values
-            .trim_matches(&['(', ')'])
-            .split_once(", ")
-            .ok_or(Error::InvalidMapFormat)?;
+        let (key, left, right) = scan!(line, "{key} = ({left}, {right})", &str, &str, &str)?;
 
         nodes.insert(key, Node { left, right });
     }
 
-    Ok((steps, nodes))
+    // Walking the map indexes `nodes` with these, so they all need an entry
+    if nodes
+        .values()
+        .any(|node| !nodes.contains_key(node.left) || !nodes.contains_key(node.right))
+    {
+        return Err(Error::InvalidMapFormat);
+    }
+
+    Ok(CamelMap { steps, nodes })
 }
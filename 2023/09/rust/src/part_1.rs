@@ -1,24 +1,11 @@
-use std::num::ParseIntError;
-
 use crate::{error::Error, shared::OASISPredictor};
 
-pub fn run(input: &str) -> Result<String, Error> {
-    let mut predictors = input
-        .lines()
-        .map(|line| {
-            let numbers = line
-                .split(' ')
-                .map(str::parse)
-                .collect::<Result<Vec<_>, ParseIntError>>()?;
-
-            Ok(OASISPredictor::from(&numbers))
-        })
-        .collect::<Result<Vec<_>, Error>>()?;
-
+#[allow(clippy::unnecessary_wraps)]
+pub fn run(predictors: &[OASISPredictor]) -> Result<String, Error> {
     let sum_of_nexts = predictors
-        .iter_mut()
-        .map(|p| p.next().unwrap())
-        .sum::<i64>();
+        .iter()
+        .map(OASISPredictor::next_value)
+        .sum::<i128>();
 
     Ok(sum_of_nexts.to_string())
 }
@@ -26,6 +13,7 @@ pub fn run(input: &str) -> Result<String, Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -34,7 +22,7 @@ mod tests {
 10 13 16 21 30 45
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|predictors| run(&predictors));
 
         assert_eq!(output, Ok("114".to_owned()));
     }
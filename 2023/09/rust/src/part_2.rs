@@ -1,33 +1,19 @@
-use std::num::ParseIntError;
-
 use crate::{error::Error, shared::OASISPredictor};
 
-pub fn run(input: &str) -> Result<String, Error> {
-    let mut predictors = input
-        .lines()
-        .map(|line| {
-            let mut numbers = line
-                .split(' ')
-                .map(str::parse)
-                .collect::<Result<Vec<_>, ParseIntError>>()?;
-
-            numbers.reverse();
-
-            Ok(OASISPredictor::from(&numbers))
-        })
-        .collect::<Result<Vec<_>, Error>>()?;
-
-    let sum_of_nexts = predictors
-        .iter_mut()
-        .map(|p| p.next().unwrap())
-        .sum::<i64>();
+#[allow(clippy::unnecessary_wraps)]
+pub fn run(predictors: &[OASISPredictor]) -> Result<String, Error> {
+    let sum_of_previous = predictors
+        .iter()
+        .map(OASISPredictor::previous_value)
+        .sum::<i128>();
 
-    Ok(sum_of_nexts.to_string())
+    Ok(sum_of_previous.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -36,7 +22,7 @@ mod tests {
 10 13 16 21 30 45
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|predictors| run(&predictors));
 
         assert_eq!(output, Ok("2".to_owned()));
     }
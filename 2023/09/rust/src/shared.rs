@@ -1,39 +1,38 @@
-#[derive(Debug)]
-pub struct OASISPredictor(Vec<i64>);
+use aoc_helpers::{parse, sequence::Sequence};
 
-impl Iterator for OASISPredictor {
-    type Item = i64;
+use crate::error::Error;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let mut counters = self.0.iter_mut().rev();
-        let mut diff = *counters.next()?;
+pub fn parse(input: &str) -> Result<Vec<OASISPredictor>, Error> {
+    parse::lines(input)
+        .map(|line| Ok(OASISPredictor::from(&line.ints()?)))
+        .collect()
+}
 
-        for counter in counters {
-            *counter += diff;
-            diff = *counter;
-        }
+/// Predicts the values around an OASIS history by extending the polynomial
+/// sequence it follows.
+#[derive(Debug)]
+pub struct OASISPredictor {
+    sequence: Sequence,
+    len: i128,
+}
 
-        Some(diff)
+impl OASISPredictor {
+    /// The value after the last one in the history
+    pub fn next_value(&self) -> i128 {
+        self.sequence.nth_term(self.len)
+    }
+
+    /// The value before the first one in the history
+    pub fn previous_value(&self) -> i128 {
+        self.sequence.nth_term(-1)
     }
 }
 
 impl From<&Vec<i64>> for OASISPredictor {
     fn from(numbers: &Vec<i64>) -> Self {
-        let mut counters = Vec::new();
-
-        for &number in numbers {
-            let mut diff = number;
-
-            for counter in &mut counters {
-                diff -= *counter;
-                *counter += diff;
-            }
-
-            if counters.is_empty() || diff != 0 {
-                counters.push(diff);
-            }
+        OASISPredictor {
+            sequence: Sequence::from_terms(numbers),
+            len: numbers.len() as i128,
         }
-
-        OASISPredictor(counters)
     }
 }
@@ -1,3 +1,4 @@
+use aoc_helpers::context::{Locate, Location};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -8,9 +9,21 @@ pub enum Error {
     #[error("could not find map start")]
     StartNotFound,
 
-    #[error("could not infer starting directions")]
-    StartInferFailed,
+    #[error("could not infer starting directions at ({x}, {y})")]
+    StartInferFailed { x: usize, y: usize },
 
-    #[error("wall doesn't loop properly")]
-    InvalidWall,
+    #[error("wall doesn't loop properly at ({x}, {y})")]
+    InvalidWall { x: usize, y: usize },
+}
+
+impl Locate for Error {
+    fn location(&self) -> Option<Location> {
+        match self {
+            Error::AocHelper(error) => error.location(),
+            Error::StartNotFound => None,
+            Error::StartInferFailed { x, y } | Error::InvalidWall { x, y } => {
+                Some(Location::cell(*x, *y))
+            }
+        }
+    }
 }
@@ -8,14 +8,15 @@ use crate::{
     shared::{direction_for_byte, find_start, infer_start_direction},
 };
 
-pub fn run(input: &str) -> Result<String, Error> {
-    let map = TextMap::parse(input)?;
-
-    let (start_x, start_y) = find_start(&map).ok_or(Error::StartNotFound)?;
+pub fn run(map: &TextMap) -> Result<String, Error> {
+    let (start_x, start_y) = find_start(map).ok_or(Error::StartNotFound)?;
     let (start_d, _) =
-        infer_start_direction(&map, start_x, start_y).ok_or(Error::StartInferFailed)?;
+        infer_start_direction(map, start_x, start_y).ok_or(Error::StartInferFailed {
+            x: start_x,
+            y: start_y,
+        })?;
 
-    Ok((compute_wall_length(&map, start_x, start_y, start_d)? / 2).to_string())
+    Ok((compute_wall_length(map, start_x, start_y, start_d)? / 2).to_string())
 }
 
 pub fn compute_wall_length(
@@ -26,7 +27,10 @@ pub fn compute_wall_length(
 ) -> Result<usize, Error> {
     let mut traveled = 0;
 
-    let mut next_direction = *start_d.cardinals().first().ok_or(Error::InvalidWall)?;
+    let mut next_direction = *start_d.cardinals().first().ok_or(Error::InvalidWall {
+        x: start_x,
+        y: start_y,
+    })?;
     let mut x = start_x;
     let mut y = start_y;
 
@@ -34,7 +38,7 @@ pub fn compute_wall_length(
         let c: &u8;
         (x, y, c) = map
             .offset_direction(x, y, next_direction)
-            .ok_or(Error::InvalidWall)?;
+            .ok_or(Error::InvalidWall { x, y })?;
 
         traveled += 1;
 
@@ -42,12 +46,12 @@ pub fn compute_wall_length(
             break;
         }
 
-        let d = direction_for_byte(*c).ok_or(Error::InvalidWall)?;
+        let d = direction_for_byte(*c).ok_or(Error::InvalidWall { x, y })?;
 
         next_direction = d ^ next_direction.reverse();
 
         if next_direction.num_cardinals() != 1 {
-            return Err(Error::InvalidWall);
+            return Err(Error::InvalidWall { x, y });
         }
     }
 
@@ -57,6 +61,7 @@ pub fn compute_wall_length(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -67,7 +72,7 @@ SJ.L7
 LJ...
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|map| run(&map));
 
         assert_eq!(output, Ok("8".to_owned()));
     }
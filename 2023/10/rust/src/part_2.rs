@@ -1,6 +1,7 @@
 use aoc_helpers::{
     map::Map2D,
     neighbors::{Direction, Grid2D, Grid2DMut},
+    text_map::TextMap,
 };
 
 use crate::{
@@ -8,12 +9,15 @@ use crate::{
     shared::{direction_for_byte, find_start, infer_start_direction},
 };
 
-pub fn run(input: &str) -> Result<String, Error> {
-    let mut map: Map2D<u8> = input.parse()?;
+pub fn run(map: &TextMap) -> Result<String, Error> {
+    let mut map = map.clone();
 
     let (start_x, start_y) = find_start(&map).ok_or(Error::StartNotFound)?;
     let (start_d, start_c) =
-        infer_start_direction(&map, start_x, start_y).ok_or(Error::StartInferFailed)?;
+        infer_start_direction(&map, start_x, start_y).ok_or(Error::StartInferFailed {
+            x: start_x,
+            y: start_y,
+        })?;
     map.set(start_x, start_y, start_c);
 
     let wall_map = compute_wall(&map, start_x, start_y, start_d)?;
@@ -22,14 +26,17 @@ pub fn run(input: &str) -> Result<String, Error> {
 }
 
 fn compute_wall(
-    map: &Map2D<u8>,
+    map: &TextMap,
     start_x: usize,
     start_y: usize,
     start_d: Direction,
 ) -> Result<Map2D<Direction>, Error> {
     let mut wall_map = Map2D::new_parallel(&map, Direction::Nowhere);
 
-    let mut next_direction = *start_d.cardinals().first().ok_or(Error::InvalidWall)?;
+    let mut next_direction = *start_d.cardinals().first().ok_or(Error::InvalidWall {
+        x: start_x,
+        y: start_y,
+    })?;
     let mut x = start_x;
     let mut y = start_y;
 
@@ -37,8 +44,8 @@ fn compute_wall(
         let c: &u8;
         (x, y, c) = map
             .offset_direction(x, y, next_direction)
-            .ok_or(Error::InvalidWall)?;
-        let d = direction_for_byte(*c).ok_or(Error::InvalidWall)?;
+            .ok_or(Error::InvalidWall { x, y })?;
+        let d = direction_for_byte(*c).ok_or(Error::InvalidWall { x, y })?;
 
         wall_map.set(x, y, d);
 
@@ -49,7 +56,7 @@ fn compute_wall(
         next_direction = d ^ next_direction.reverse();
 
         if next_direction.num_cardinals() != 1 {
-            return Err(Error::InvalidWall);
+            return Err(Error::InvalidWall { x, y });
         }
     }
 
@@ -76,6 +83,7 @@ fn count_inside(wall_map: &Map2D<Direction>) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example_1() {
@@ -90,7 +98,7 @@ mod tests {
 ...........
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|map| run(&map));
 
         assert_eq!(output, Ok("4".to_owned()));
     }
@@ -109,7 +117,7 @@ L--J.L7...LJS7F-7L7.
 ....L---J.LJ.LJLJ...
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|map| run(&map));
 
         assert_eq!(output, Ok("8".to_owned()));
     }
@@ -128,7 +136,7 @@ L.L7LFJ|||||FJL7||LJ
 L7JLJL-JLJLJL--JLJ.L
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|map| run(&map));
 
         assert_eq!(output, Ok("10".to_owned()));
     }
@@ -1,4 +1,13 @@
-use aoc_helpers::neighbors::{Direction, Grid2D};
+use aoc_helpers::{
+    neighbors::{Direction, Grid2D},
+    text_map::TextMap,
+};
+
+use crate::error::Error;
+
+pub fn parse(input: &str) -> Result<TextMap<'_>, Error> {
+    Ok(TextMap::parse(input)?)
+}
 
 pub fn direction_for_byte(c: u8) -> Option<Direction> {
     match c {
@@ -1,3 +1,4 @@
+use aoc_helpers::context::Locate;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -8,3 +9,5 @@ pub enum Error {
     #[error("expansion factor of 0 not allowed (it would collapse the universe and make everything crash into each-other, destroying everything you ever knew and loved...)")]
     NoExpansion,
 }
+
+impl Locate for Error {}
@@ -1,12 +1,16 @@
-use crate::{error::Error, shared::run_with_expansion_factor};
+use crate::{
+    error::Error,
+    shared::{run_with_expansion_factor, Universe},
+};
 
-pub fn run(input: &str) -> Result<String, Error> {
-    run_with_expansion_factor(input, 2)
+pub fn run(universe: &Universe) -> Result<String, Error> {
+    run_with_expansion_factor(universe, 2)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -22,7 +26,7 @@ mod tests {
 #...#.....
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|universe| run(&universe));
 
         assert_eq!(output, Ok("374".to_owned()));
     }
@@ -1,12 +1,16 @@
-use crate::{error::Error, shared::run_with_expansion_factor};
+use crate::{
+    error::Error,
+    shared::{run_with_expansion_factor, Universe},
+};
 
-pub fn run(input: &str) -> Result<String, Error> {
-    run_with_expansion_factor(input, 1_000_000)
+pub fn run(universe: &Universe) -> Result<String, Error> {
+    run_with_expansion_factor(universe, 1_000_000)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example_1() {
@@ -22,7 +26,7 @@ mod tests {
 #...#.....
 ";
 
-        let output = run_with_expansion_factor(input, 10);
+        let output = parse(input).and_then(|universe| run_with_expansion_factor(&universe, 10));
 
         assert_eq!(output, Ok("1030".to_owned()));
     }
@@ -41,7 +45,7 @@ mod tests {
 #...#.....
 ";
 
-        let output = run_with_expansion_factor(input, 100);
+        let output = parse(input).and_then(|universe| run_with_expansion_factor(&universe, 100));
 
         assert_eq!(output, Ok("8410".to_owned()));
     }
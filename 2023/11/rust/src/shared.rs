@@ -1,38 +1,58 @@
-use aoc_helpers::{neighbors::Grid2D, text_map::TextMap};
+use aoc_helpers::{bit_grid::BitGrid, neighbors::Grid2D, text_map::TextMap};
 
 use crate::error::Error;
 
-pub fn run_with_expansion_factor(input: &str, expansion_factor: usize) -> Result<String, Error> {
-    if expansion_factor == 0 {
-        return Err(Error::NoExpansion);
-    }
+/// Where the galaxies are before expanding, and how many empty columns and
+/// rows come before each column and row
+pub struct Universe {
+    galaxies: Vec<(usize, usize)>,
+    column_expansion: Vec<usize>,
+    row_expansion: Vec<usize>,
+}
 
+pub fn parse(input: &str) -> Result<Universe, Error> {
     let collapsed_universe = TextMap::parse(input)?;
+    let galaxy_map = BitGrid::from_grid(&collapsed_universe, |&c| c == b'#');
 
-    let mut empty_columns = vec![true; collapsed_universe.width()];
-    let mut empty_rows = vec![true; collapsed_universe.height()];
+    let empty_columns = (0..galaxy_map.width())
+        .map(|x| galaxy_map.count_column(x) == 0)
+        .collect::<Vec<_>>();
+    let empty_rows = (0..galaxy_map.height())
+        .map(|y| galaxy_map.count_row(y) == 0)
+        .collect::<Vec<_>>();
 
-    for (x, y, &c) in collapsed_universe.iter() {
-        if c == b'#' {
-            empty_columns[x] = false;
-            empty_rows[y] = false;
-        }
-    }
-
-    let column_expansion = accumulate_expansion(&empty_columns);
-    let row_expansion = accumulate_expansion(&empty_rows);
+    let galaxies = galaxy_map
+        .iter()
+        .filter(|&(_, _, &is_galaxy)| is_galaxy)
+        .map(|(x, y, _)| (x, y))
+        .collect();
 
-    let mut galaxies = Vec::new();
+    Ok(Universe {
+        galaxies,
+        column_expansion: accumulate_expansion(&empty_columns),
+        row_expansion: accumulate_expansion(&empty_rows),
+    })
+}
 
-    for (x, y, &c) in collapsed_universe.iter() {
-        if c == b'#' {
-            galaxies.push((
-                x + column_expansion[x] * (expansion_factor - 1),
-                y + row_expansion[y] * (expansion_factor - 1),
-            ));
-        }
+pub fn run_with_expansion_factor(
+    universe: &Universe,
+    expansion_factor: usize,
+) -> Result<String, Error> {
+    if expansion_factor == 0 {
+        return Err(Error::NoExpansion);
     }
 
+    let galaxies = universe
+        .galaxies
+        .iter()
+        .map(|&(x, y)| {
+            (
+                x + universe.column_expansion[x] * (expansion_factor - 1),
+                y + universe.row_expansion[y] * (expansion_factor - 1),
+            )
+        })
+        .collect::<Vec<_>>();
+
     let mut total_distances = 0;
 
     for (i, &(a_x, a_y)) in galaxies.iter().enumerate() {
@@ -1,19 +1,14 @@
-use std::collections::HashMap;
+use crate::{
+    error::Error,
+    shared::{Record, SpringRow},
+};
 
-use crate::{error::Error, shared::SpringRow};
-
-pub fn run(input: &str) -> Result<String, Error> {
+#[allow(clippy::unnecessary_wraps)]
+pub fn run(records: &[Record]) -> Result<String, Error> {
     let mut total_possibilities = 0;
 
-    let rows = input
-        .lines()
-        .map(|line| SpringRow::parse_line(line, 5))
-        .collect::<Result<Vec<_>, Error>>()?;
-
-    let mut memoized = HashMap::new();
-
-    for row in &rows {
-        total_possibilities += row.as_partial().compute_possibilities(&mut memoized);
+    for record in records {
+        total_possibilities += SpringRow::unfold(record, 5).possibilities();
     }
 
     Ok(total_possibilities.to_string())
@@ -22,6 +17,8 @@ pub fn run(input: &str) -> Result<String, Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
+    use aoc_helpers::parse::Span;
 
     #[test]
     fn example() {
@@ -33,7 +30,7 @@ mod tests {
 ?###???????? 3,2,1
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|records| run(&records));
 
         assert_eq!(output, Ok("525152".to_owned()));
     }
@@ -46,10 +43,10 @@ mod tests {
                 $(
                     #[test]
                     fn $name() {
-                        let row = SpringRow::parse_line($input, 5).unwrap();
-                        let mut memo = HashMap::new();
+                        let record = Record::parse(Span::new($input)).unwrap();
+                        let row = SpringRow::unfold(&record, 5);
 
-                        assert_eq!(row.as_partial().compute_possibilities(&mut memo), $expected);
+                        assert_eq!(row.possibilities(), $expected);
                     }
                 )*
             }
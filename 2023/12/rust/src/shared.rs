@@ -1,16 +1,41 @@
-use std::collections::HashMap;
+use aoc_helpers::{
+    hash::FxBuildHasher,
+    memo::Memo,
+    parse::{self, Span},
+    scan,
+};
 
 use crate::error::Error;
 
+pub fn parse(input: &str) -> Result<Vec<Record<'_>>, Error> {
+    parse::lines(input).map(Record::parse).collect()
+}
+
+/// A row as written, before it's unfolded
+pub struct Record<'a> {
+    springs: &'a str,
+    counts: Vec<usize>,
+}
+
+impl Record<'_> {
+    pub fn parse(line: Span) -> Result<Record, Error> {
+        let (springs, counts) = scan!(line, "{springs} {counts}", &str, Span)?;
+
+        Ok(Record {
+            springs,
+            counts: counts.ints::<usize>()?,
+        })
+    }
+}
+
 pub struct SpringRow {
     source: String,
     match_str: String,
 }
 
 impl SpringRow {
-    pub fn parse_line(line: &str, folds: usize) -> Result<SpringRow, Error> {
-        let (source_folded, match_nums) = line.split_once(' ').ok_or(Error::InvalidLine)?;
-
+    /// The row with its springs and counts repeated `folds` times
+    pub fn unfold(record: &Record, folds: usize) -> SpringRow {
         let mut source = String::new();
         let mut match_str = ".".to_owned();
 
@@ -19,10 +44,10 @@ impl SpringRow {
                 source.push('?');
             }
 
-            source.push_str(source_folded);
+            source.push_str(record.springs);
 
-            for num_str in match_nums.split(',') {
-                for _ in 0..num_str.parse()? {
+            for &num in &record.counts {
+                for _ in 0..num {
                     match_str.push('#');
                 }
 
@@ -30,81 +55,78 @@ impl SpringRow {
             }
         }
 
-        Ok(SpringRow { source, match_str })
+        SpringRow { source, match_str }
     }
 
-    pub fn as_partial(&self) -> SpringPartial {
-        SpringPartial {
-            source: &self.source,
-            match_str: &self.match_str,
-            last_was_hash: false,
-        }
+    /// The number of ways to fill in the unknown springs that match the counts
+    pub fn possibilities(&self) -> u64 {
+        let mut memo = Memo::with_capacity_and_hasher(
+            self.source.len() * self.match_str.len(),
+            FxBuildHasher::default(),
+        );
+
+        memo.compute(
+            SpringPartial {
+                source: 0,
+                match_str: 0,
+                last_was_hash: false,
+            },
+            &|partial, recurse| partial.possibilities(self, recurse),
+        )
     }
 }
 
-#[derive(PartialEq, Eq, Hash, Clone)]
-pub struct SpringPartial<'a> {
-    pub source: &'a str,
-    pub match_str: &'a str,
-    pub last_was_hash: bool,
+/// How far through a row's springs and pattern a match has got
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+struct SpringPartial {
+    source: usize,
+    match_str: usize,
+    last_was_hash: bool,
 }
 
-impl<'a> SpringPartial<'a> {
-    pub fn compute_possibilities(&self, memoized: &mut HashMap<SpringPartial<'a>, u64>) -> u64 {
-        if let Some(&memoized_possibilities) = memoized.get(self) {
-            return memoized_possibilities;
-        }
+impl SpringPartial {
+    fn possibilities(self, row: &SpringRow, recurse: &mut dyn FnMut(SpringPartial) -> u64) -> u64 {
+        let source = &row.source.as_bytes()[self.source..];
+        let match_str = &row.match_str.as_bytes()[self.match_str..];
 
-        let out = match self.match_str.as_bytes().first() {
-            None => {
-                u64::from(self.source.is_empty())
-            }
+        match match_str.first() {
+            None => u64::from(source.is_empty()),
             Some(b'.') => {
-                let max_offset = self.source.len()
-                    - match self.match_str.len() {
+                let max_offset = source.len()
+                    - match match_str.len() {
                         0 => unreachable!(),
                         1 => 0,
                         2 => panic!("len 2 match_str starts with '.'"),
-                        3.. => self.match_str.len() - 2,
+                        3.. => match_str.len() - 2,
                     };
 
                 let mut possibilities = 0;
 
                 for offset in 0..=max_offset {
-                    if !(offset == 0 && self.last_was_hash) || self.source.is_empty() {
-                        possibilities += SpringPartial {
-                            source: &self.source[offset..],
-                            match_str: &self.match_str[1..],
+                    if !(offset == 0 && self.last_was_hash) || source.is_empty() {
+                        possibilities += recurse(SpringPartial {
+                            source: self.source + offset,
+                            match_str: self.match_str + 1,
                             last_was_hash: false,
-                        }
-                        .compute_possibilities(memoized);
+                        });
                     }
 
-                    if let Some(b'#') = self.source.as_bytes().get(offset) {
+                    if let Some(b'#') = source.get(offset) {
                         break;
                     }
                 }
 
                 possibilities
             }
-            Some(b'#') => {
-                let source_byte = self.source.as_bytes().first().copied();
-
-                match source_byte {
-                    Some(b'#' | b'?') => SpringPartial {
-                        source: &self.source[1..],
-                        match_str: &self.match_str[1..],
-                        last_was_hash: true,
-                    }
-                    .compute_possibilities(memoized),
-                    _ => 0,
-                }
-            }
+            Some(b'#') => match source.first() {
+                Some(b'#' | b'?') => recurse(SpringPartial {
+                    source: self.source + 1,
+                    match_str: self.match_str + 1,
+                    last_was_hash: true,
+                }),
+                _ => 0,
+            },
             Some(byte) => panic!("unexpected match_str byte {byte}"),
-        };
-
-        memoized.insert(self.clone(), out);
-
-        out
+        }
     }
 }
@@ -1,3 +1,4 @@
+use aoc_helpers::context::Locate;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -5,3 +6,5 @@ pub enum Error {
     #[error("{0}")]
     AocHelper(#[from] aoc_helpers::Error),
 }
+
+impl Locate for Error {}
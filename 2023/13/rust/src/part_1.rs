@@ -1,17 +1,17 @@
 use aoc_helpers::text_map::TextMap;
 
-use crate::{error::Error, shared::{hash_columns, hash_rows}};
-
-pub fn run(input: &str) -> Result<String, Error> {
-    let boards = input.trim().split("\n\n").map(TextMap::parse);
+use crate::{
+    error::Error,
+    shared::{hash_columns, hash_rows},
+};
 
+#[allow(clippy::unnecessary_wraps)]
+pub fn run(boards: &[TextMap]) -> Result<String, Error> {
     let mut sum = 0;
 
     for board in boards {
-        let board = board?;
-
-        let columns = hash_columns(&board);
-        let rows = hash_rows(&board);
+        let columns = hash_columns(board);
+        let rows = hash_rows(board);
 
         if let Some(i) = find_reflection(&columns) {
             sum += i + 1;
@@ -54,6 +54,7 @@ fn find_reflection(input: &[u64]) -> Option<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -74,7 +75,7 @@ mod tests {
 #....#..#
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|boards| run(&boards));
 
         assert_eq!(output, Ok("405".to_owned()));
     }
@@ -89,7 +90,7 @@ mod tests {
 ..##..##.
 #.#.##.#.";
 
-        let output = run(input);
+        let output = parse(input).and_then(|boards| run(&boards));
 
         assert_eq!(output, Ok("5".to_owned()));
     }
@@ -105,7 +106,7 @@ mod tests {
 #....#..#
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|boards| run(&boards));
 
         assert_eq!(output, Ok("400".to_owned()));
     }
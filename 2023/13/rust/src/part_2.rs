@@ -5,26 +5,23 @@ use crate::{
     shared::{hash_columns, hash_rows},
 };
 
-pub fn run(input: &str) -> Result<String, Error> {
-    let boards = input.trim().split("\n\n").map(TextMap::parse);
-
+#[allow(clippy::unnecessary_wraps)]
+pub fn run(boards: &[TextMap]) -> Result<String, Error> {
     let mut sum = 0;
 
     for board in boards {
-        let board = board?;
-
-        let columns = hash_columns(&board);
-        let rows = hash_rows(&board);
+        let columns = hash_columns(board);
+        let rows = hash_rows(board);
 
         for (i, l, r) in find_possible_smudged_reflections(&columns) {
-            if validate_smudged_column(&board, l, r) {
+            if validate_smudged_column(board, l, r) {
                 sum += i + 1;
                 break;
             }
         }
 
         for (i, t, b) in find_possible_smudged_reflections(&rows) {
-            if validate_smudged_row(&board, t, b) {
+            if validate_smudged_row(board, t, b) {
                 sum += (i + 1) * 100;
                 break;
             }
@@ -101,6 +98,7 @@ fn validate_smudged_row(board: &TextMap, t: usize, b: usize) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
@@ -121,7 +119,7 @@ mod tests {
 #....#..#
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|boards| run(&boards));
 
         assert_eq!(output, Ok("400".to_owned()));
     }
@@ -136,7 +134,7 @@ mod tests {
 ..##..##.
 #.#.##.#.";
 
-        let output = run(input);
+        let output = parse(input).and_then(|boards| run(&boards));
 
         assert_eq!(output, Ok("300".to_owned()));
     }
@@ -152,7 +150,7 @@ mod tests {
 #....#..#
 ";
 
-        let output = run(input);
+        let output = parse(input).and_then(|boards| run(&boards));
 
         assert_eq!(output, Ok("100".to_owned()));
     }
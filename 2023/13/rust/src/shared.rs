@@ -1,9 +1,19 @@
-use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+use std::hash::Hasher;
 
-use aoc_helpers::{text_map::TextMap, neighbors::Grid2D};
+use aoc_helpers::{
+    hash::{hash_slice, FxHasher},
+    neighbors::Grid2D,
+    text_map::{TextMap, TextMapOptions},
+};
+
+use crate::error::Error;
+
+pub fn parse(input: &str) -> Result<Vec<TextMap<'_>>, Error> {
+    Ok(TextMapOptions::new().parse_many(input)?)
+}
 
 pub fn hash_column(board: &TextMap, x: usize) -> u64 {
-    let mut hasher = DefaultHasher::new();
+    let mut hasher = FxHasher::default();
 
     for y in 0..board.height() {
         hasher.write_u8(*board.get(x, y).unwrap());
@@ -13,23 +23,13 @@ pub fn hash_column(board: &TextMap, x: usize) -> u64 {
 }
 
 pub fn hash_row(board: &TextMap, y: usize) -> u64 {
-    let mut hasher = DefaultHasher::new();
-
-    for x in 0..board.width() {
-        hasher.write_u8(*board.get(x, y).unwrap());
-    }
-
-    hasher.finish()
+    hash_slice(board.row(y).unwrap())
 }
 
 pub fn hash_columns(board: &TextMap) -> Vec<u64> {
-    (0..board.width())
-        .map(|x| hash_column(board, x))
-        .collect()
+    (0..board.width()).map(|x| hash_column(board, x)).collect()
 }
 
 pub fn hash_rows(board: &TextMap) -> Vec<u64> {
-    (0..board.height())
-        .map(|y| hash_row(board, y))
-        .collect()
+    (0..board.height()).map(|y| hash_row(board, y)).collect()
 }
@@ -313,6 +313,7 @@ Usage:
 ./advent.sh prepare [year] [day]
 ./advent.sh run [year] [day] [langs...]
 ./advent.sh test [year] [day] [langs...]
+./advent.sh runner <command> [options]
 EOF
 
   return 1
@@ -338,6 +339,10 @@ case "$COMMAND" in
     with_year test "$@"
     ;;
 
+  runner)
+    cargo run --release -q --manifest-path other/runner/rust/Cargo.toml -- "$@"
+    ;;
+
   *)
     help
     ;;
@@ -1,3 +1,7 @@
+use std::{fmt::Write, str::FromStr, time::Duration};
+
+use thiserror::Error;
+
 #[allow(clippy::module_name_repetitions)]
 pub trait AocAnswer {
     fn print_aoc_answer(&self, part_number: i32);
@@ -20,3 +24,181 @@ impl AocAnswer for String {
         println!("Part {part_number}: {self}");
     }
 }
+
+/// How answers are printed: as `Part 1: ...` lines for people, or as records
+/// for other tools
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+    /// Tab-separated `year`, `day`, `part`, `answer`, `error`, `duration`,
+    /// `language` and `parse_duration` columns, without a header
+    Tsv,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("unknown output format {0:?}, expected text, json or tsv")]
+pub struct UnknownFormat(pub String);
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(UnknownFormat(format.to_owned())),
+        }
+    }
+}
+
+/// The outcome of one part of one day, as printed by `Format`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnswerRecord {
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+    pub part: u8,
+    /// The answer, or the error message
+    pub answer: Result<String, String>,
+    pub duration: Option<Duration>,
+    /// The language's directory name, like `rust`
+    pub language: String,
+    /// How long parsing the input took, for days that parse it once for both
+    /// parts
+    pub parse_duration: Option<Duration>,
+}
+
+impl AnswerRecord {
+    /// The record as a line in `format`, without a trailing newline. Durations
+    /// are in seconds.
+    #[must_use]
+    pub fn line(&self, format: Format) -> String {
+        let (answer, error) = match &self.answer {
+            Ok(answer) => (Some(answer.as_str()), None),
+            Err(error) => (None, Some(error.as_str())),
+        };
+        let duration = self.duration.map(|duration| duration.as_secs_f64());
+        let parse_duration = self.parse_duration.map(|duration| duration.as_secs_f64());
+
+        match format {
+            Format::Text => match error {
+                None => format!("Part {}: {}", self.part, answer.unwrap_or_default()),
+                Some(error) => format!("Part {} error!: {error}", self.part),
+            },
+            Format::Json => format!(
+                "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"error\":{},\"duration\":{},\"language\":{},\"parse_duration\":{}}}",
+                json_value(self.year),
+                json_value(self.day),
+                self.part,
+                json_value(answer.map(json_string)),
+                json_value(error.map(json_string)),
+                json_value(duration),
+                json_string(&self.language),
+                json_value(parse_duration),
+            ),
+            Format::Tsv => [
+                self.year.map(|year| year.to_string()),
+                self.day.map(|day| day.to_string()),
+                Some(self.part.to_string()),
+                answer.map(tsv_field),
+                error.map(tsv_field),
+                duration.map(|duration| duration.to_string()),
+                Some(tsv_field(&self.language)),
+                parse_duration.map(|duration| duration.to_string()),
+            ]
+            .map(Option::unwrap_or_default)
+            .join("\t"),
+        }
+    }
+}
+
+fn json_value<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "null".to_owned(), |value| value.to_string())
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{:04x}", u32::from(c)).unwrap(),
+            c => out.push(c),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+/// Escapes the characters that would break a TSV row
+fn tsv_field(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(answer: Result<&str, &str>) -> AnswerRecord {
+        AnswerRecord {
+            year: Some(2023),
+            day: Some(6),
+            part: 2,
+            answer: answer.map(str::to_owned).map_err(str::to_owned),
+            duration: Some(Duration::from_millis(1500)),
+            language: "rust".to_owned(),
+            parse_duration: Some(Duration::from_millis(250)),
+        }
+    }
+
+    macro_rules! line_test {
+        ($suite:ident, $($name:ident: $record:expr, $format:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!($record.line($format), $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    line_test!(line_tests,
+        text_answer: record(Ok("71503")), Format::Text => "Part 2: 71503",
+        text_error: record(Err("no races")), Format::Text => "Part 2 error!: no races",
+        json_answer: record(Ok("71503")), Format::Json
+            => r#"{"year":2023,"day":6,"part":2,"answer":"71503","error":null,"duration":1.5,"language":"rust","parse_duration":0.25}"#,
+        json_error: record(Err("bad \"time\"\n  at line 1\u{7}")), Format::Json
+            => r#"{"year":2023,"day":6,"part":2,"answer":null,"error":"bad \"time\"\n  at line 1\u0007","duration":1.5,"language":"rust","parse_duration":0.25}"#,
+        json_unknown_day: AnswerRecord { year: None, day: None, duration: None, parse_duration: None, ..record(Ok("1")) }, Format::Json
+            => r#"{"year":null,"day":null,"part":2,"answer":"1","error":null,"duration":null,"language":"rust","parse_duration":null}"#,
+        tsv_answer: record(Ok("71503")), Format::Tsv => "2023\t6\t2\t71503\t\t1.5\trust\t0.25",
+        tsv_error: record(Err("a\tb\nc")), Format::Tsv => "2023\t6\t2\t\ta\\tb\\nc\t1.5\trust\t0.25",
+        tsv_unparsed: AnswerRecord { parse_duration: None, ..record(Ok("1")) }, Format::Tsv => "2023\t6\t2\t1\t\t1.5\trust\t",
+    );
+
+    #[test]
+    fn parses_format() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("tsv".parse(), Ok(Format::Tsv));
+        assert_eq!(
+            "xml".parse::<Format>().unwrap_err().to_string(),
+            "unknown output format \"xml\", expected text, json or tsv"
+        );
+    }
+}
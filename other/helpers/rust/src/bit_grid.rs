@@ -0,0 +1,392 @@
+use std::{
+    fmt,
+    ops::{BitAndAssign, BitOrAssign, BitXorAssign, Not},
+};
+
+use crate::neighbors::{Direction, Grid2D, Grid2DMut};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A grid of booleans packed into one bit per cell.
+///
+/// Each row starts on a fresh `u64` word, with `x` stored in bit `x % 64` of
+/// word `x / 64`. Bits past the end of a row are always kept clear, so rows
+/// can be compared, hashed and counted a word at a time.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[allow(clippy::module_name_repetitions)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    width: usize,
+    height: usize,
+    row_words: usize,
+}
+
+impl BitGrid {
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> BitGrid {
+        let row_words = width.div_ceil(WORD_BITS);
+
+        BitGrid {
+            words: vec![0; row_words * height],
+            width,
+            height,
+            row_words,
+        }
+    }
+
+    pub fn new_parallel<M>(other: &M) -> BitGrid
+    where
+        M: Grid2D,
+    {
+        BitGrid::new(other.width(), other.height())
+    }
+
+    /// Builds a grid with the same shape as `other`, setting the cells where
+    /// `predicate` returns true
+    pub fn from_grid<M, F>(other: &M, mut predicate: F) -> BitGrid
+    where
+        M: Grid2D,
+        F: FnMut(&M::Item) -> bool,
+    {
+        let mut grid = BitGrid::new_parallel(other);
+
+        for (x, y, item) in other.iter() {
+            if predicate(item) {
+                grid.set(x, y, true);
+            }
+        }
+
+        grid
+    }
+
+    /// The packed words of row `y`
+    #[must_use]
+    pub fn row(&self, y: usize) -> Option<&[u64]> {
+        if y < self.height {
+            Some(&self.words[y * self.row_words..(y + 1) * self.row_words])
+        } else {
+            None
+        }
+    }
+
+    /// Modifies the packed words of row `y` in place, clearing any bits `f`
+    /// sets past the end of the row
+    pub fn update_row<F>(&mut self, y: usize, f: F)
+    where
+        F: FnOnce(&mut [u64]),
+    {
+        if y < self.height {
+            f(&mut self.words[y * self.row_words..(y + 1) * self.row_words]);
+            self.clear_padding(y);
+        }
+    }
+
+    #[must_use]
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[must_use]
+    pub fn count_row(&self, y: usize) -> usize {
+        self.row(y)
+            .map_or(0, |row| row.iter().map(|w| w.count_ones() as usize).sum())
+    }
+
+    #[must_use]
+    pub fn count_column(&self, x: usize) -> usize {
+        if x >= self.width {
+            return 0;
+        }
+
+        (0..self.height).filter(|&y| self.bit(x, y)).count()
+    }
+
+    /// Moves every cell one step in `direction`, dropping cells that fall off
+    /// the edge. Summing the shifts of a grid in each direction counts the
+    /// neighbors of every cell at once.
+    #[must_use]
+    pub fn shifted(&self, mut direction: Direction) -> BitGrid {
+        // Opposite directions cancel out, the same as `offset_direction`
+        if direction & Direction::LeftRight == Direction::LeftRight {
+            direction ^= Direction::LeftRight;
+        }
+        if direction & Direction::UpDown == Direction::UpDown {
+            direction ^= Direction::UpDown;
+        }
+
+        let mut out = self.clone();
+
+        for &d in direction.cardinals() {
+            out = match d {
+                Direction::Up => out.shifted_rows(false),
+                Direction::Down => out.shifted_rows(true),
+                Direction::Left => out.shifted_columns(false),
+                _ => out.shifted_columns(true),
+            };
+        }
+
+        out
+    }
+
+    fn shifted_rows(&self, down: bool) -> BitGrid {
+        let mut out = BitGrid::new(self.width, self.height);
+
+        if self.height > 0 {
+            let moved = (self.height - 1) * self.row_words;
+
+            if down {
+                out.words[self.row_words..].copy_from_slice(&self.words[..moved]);
+            } else {
+                out.words[..moved].copy_from_slice(&self.words[self.row_words..]);
+            }
+        }
+
+        out
+    }
+
+    fn shifted_columns(&self, right: bool) -> BitGrid {
+        let mut out = self.clone();
+
+        for y in 0..self.height {
+            out.update_row(y, |row| {
+                if right {
+                    let mut carry = 0;
+
+                    for word in row.iter_mut() {
+                        let next_carry = *word >> (WORD_BITS - 1);
+                        *word = (*word << 1) | carry;
+                        carry = next_carry;
+                    }
+                } else {
+                    let mut carry = 0;
+
+                    for word in row.iter_mut().rev() {
+                        let next_carry = *word & 1;
+                        *word = (*word >> 1) | (carry << (WORD_BITS - 1));
+                        carry = next_carry;
+                    }
+                }
+            });
+        }
+
+        out
+    }
+
+    fn bit(&self, x: usize, y: usize) -> bool {
+        let word = self.words[y * self.row_words + x / WORD_BITS];
+
+        word & (1 << (x % WORD_BITS)) != 0
+    }
+
+    fn clear_padding(&mut self, y: usize) {
+        let used_bits = self.width % WORD_BITS;
+
+        if used_bits != 0 {
+            self.words[(y + 1) * self.row_words - 1] &= (1 << used_bits) - 1;
+        }
+    }
+
+    fn zip_words<F>(&mut self, other: &BitGrid, mut f: F)
+    where
+        F: FnMut(&mut u64, u64),
+    {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "bit grids must be the same size"
+        );
+
+        for (word, &other_word) in self.words.iter_mut().zip(&other.words) {
+            f(word, other_word);
+        }
+    }
+}
+
+impl Grid2D for BitGrid {
+    type Item = bool;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<&bool> {
+        if x < self.width && y < self.height {
+            Some(if self.bit(x, y) { &true } else { &false })
+        } else {
+            None
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (usize, usize, &bool)> {
+        (0..self.height).flat_map(move |y| {
+            (0..self.width).map(move |x| (x, y, if self.bit(x, y) { &true } else { &false }))
+        })
+    }
+}
+
+impl Grid2DMut for BitGrid {
+    fn set(&mut self, x: usize, y: usize, item: bool) -> Option<bool> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let word = &mut self.words[y * self.row_words + x / WORD_BITS];
+        let mask = 1 << (x % WORD_BITS);
+        let old = *word & mask != 0;
+
+        if item {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+
+        Some(old)
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, rhs: &BitGrid) {
+        self.zip_words(rhs, |word, other| *word &= other);
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, rhs: &BitGrid) {
+        self.zip_words(rhs, |word, other| *word |= other);
+    }
+}
+
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, rhs: &BitGrid) {
+        self.zip_words(rhs, |word, other| *word ^= other);
+    }
+}
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut out = self.clone();
+
+        for y in 0..out.height {
+            out.update_row(y, |row| row.iter_mut().for_each(|word| *word = !*word));
+        }
+
+        out
+    }
+}
+
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                f.write_str(if self.bit(x, y) { "#" } else { "." })?;
+            }
+
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text_map::TextMap;
+
+    fn grid(input: &str) -> BitGrid {
+        BitGrid::from_grid(&TextMap::parse(input).unwrap(), |&c| c == b'#')
+    }
+
+    #[test]
+    fn set_and_get() {
+        let mut grid = BitGrid::new(70, 2);
+
+        assert_eq!(grid.set(65, 1, true), Some(false));
+        assert_eq!(grid.set(65, 1, true), Some(true));
+        assert_eq!(grid.set(70, 1, true), None);
+
+        assert_eq!(grid.get(65, 1), Some(&true));
+        assert_eq!(grid.get(64, 1), Some(&false));
+        assert_eq!(grid.count_ones(), 1);
+    }
+
+    #[test]
+    fn counts() {
+        let grid = grid("#.#\n..#\n");
+
+        assert_eq!(grid.count_row(0), 2);
+        assert_eq!(grid.count_row(1), 1);
+        assert_eq!(grid.count_column(0), 1);
+        assert_eq!(grid.count_column(1), 0);
+        assert_eq!(grid.count_column(2), 2);
+    }
+
+    #[test]
+    fn not_keeps_padding_clear() {
+        let grid = !&BitGrid::new(3, 2);
+
+        assert_eq!(grid.count_ones(), 6);
+        assert_eq!(grid.to_string(), "###\n###\n");
+    }
+
+    macro_rules! shifted_test {
+        ($suite:ident, $($name:ident: $direction:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        let grid = grid("#..\n.#.\n..#\n");
+
+                        assert_eq!(grid.shifted($direction).to_string(), $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    shifted_test!(shifted_tests,
+        nowhere: Direction::Nowhere => "#..\n.#.\n..#\n",
+        up: Direction::Up => ".#.\n..#\n...\n",
+        down: Direction::Down => "...\n#..\n.#.\n",
+        left: Direction::Left => "...\n#..\n.#.\n",
+        right: Direction::Right => ".#.\n..#\n...\n",
+        down_right: Direction::DownRight => "...\n.#.\n..#\n",
+        up_left: Direction::UpLeft => "#..\n.#.\n...\n",
+        up_down: Direction::UpDown => "#..\n.#.\n..#\n",
+    );
+
+    #[test]
+    fn shift_across_words() {
+        let mut grid = BitGrid::new(130, 1);
+        grid.set(63, 0, true);
+        grid.set(129, 0, true);
+
+        let right = grid.shifted(Direction::Right);
+        assert_eq!(right.get(64, 0), Some(&true));
+        assert_eq!(right.count_ones(), 1);
+
+        let left = right.shifted(Direction::Left);
+        assert_eq!(left.get(63, 0), Some(&true));
+        assert_eq!(left.count_ones(), 1);
+    }
+
+    #[test]
+    fn bitwise_ops() {
+        let mut a = grid("##.\n...\n");
+        let b = grid(".##\n..#\n");
+
+        let mut xor = a.clone();
+        xor ^= &b;
+        assert_eq!(xor.to_string(), "#.#\n..#\n");
+
+        a &= &b;
+        assert_eq!(a.to_string(), ".#.\n...\n");
+    }
+}
@@ -0,0 +1,139 @@
+/// A map cell that is written as a single byte of text
+pub trait CellByte: Sized {
+    fn from_byte(byte: u8) -> Option<Self>;
+    fn to_byte(&self) -> u8;
+}
+
+impl CellByte for u8 {
+    fn from_byte(byte: u8) -> Option<Self> {
+        Some(byte)
+    }
+
+    fn to_byte(&self) -> u8 {
+        *self
+    }
+}
+
+#[doc(hidden)]
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub const fn ascii_byte(c: char) -> u8 {
+    assert!(c.is_ascii(), "map cell characters must be ASCII");
+
+    c as u8
+}
+
+/// Declares a map cell enum from a table of characters, implementing
+/// [`CellByte`] and `Display` for it.
+///
+/// ```
+/// aoc_helpers::cell_enum! {
+///     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///     pub enum Cell {
+///         '#' => Wall,
+///         '.' => Open,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! cell_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($char:literal => $variant:ident),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variant),*
+        }
+
+        impl $crate::cell::CellByte for $name {
+            fn from_byte(byte: u8) -> Option<Self> {
+                $(
+                    if byte == const { $crate::cell::ascii_byte($char) } {
+                        return Some($name::$variant);
+                    }
+                )*
+
+                None
+            }
+
+            fn to_byte(&self) -> u8 {
+                match self {
+                    $($name::$variant => const { $crate::cell::ascii_byte($char) }),*
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                use std::fmt::Write;
+
+                f.write_char(char::from($crate::cell::CellByte::to_byte(self)))
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{map::Map2D, neighbors::Grid2D, Error};
+
+    cell_enum! {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        enum Cell {
+            '#' => Wall,
+            '.' => Open,
+            'S' => Start,
+        }
+    }
+
+    #[test]
+    fn parse_cells() {
+        let map = Map2D::<Cell>::parse_cells("#.\nS#\n").unwrap();
+
+        assert_eq!(map.get(1, 0), Some(&Cell::Open));
+        assert_eq!(map.get(0, 1), Some(&Cell::Start));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let input = "#.#\n.S.\n";
+        let map = Map2D::<Cell>::parse_cells(input).unwrap();
+
+        assert_eq!(map.to_string(), input);
+    }
+
+    #[test]
+    fn unknown_byte_position() {
+        let output = Map2D::<Cell>::parse_cells("#.\n.?\n");
+
+        assert_eq!(
+            output.err(),
+            Some(Error::InvalidCell {
+                line: 2,
+                column: 2,
+                byte: b'?',
+                reason: "unknown cell".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_with_decoder_error() {
+        let output = Map2D::parse_with("12\n3x\n", |byte| {
+            char::from(byte).to_digit(10).ok_or("not a digit")
+        });
+
+        assert_eq!(
+            output.err(),
+            Some(Error::InvalidCell {
+                line: 2,
+                column: 2,
+                byte: b'x',
+                reason: "not a digit".to_owned(),
+            })
+        );
+    }
+}
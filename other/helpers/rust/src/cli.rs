@@ -0,0 +1,513 @@
+use std::{
+    borrow::Borrow,
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use thiserror::Error;
+
+use crate::{
+    answer::{AnswerRecord, Format, UnknownFormat},
+    context::{Locate, WithInput},
+};
+
+/// Where a day's input is read from, relative to its `rust` directory
+pub const DEFAULT_INPUT: &str = "../input.txt";
+
+pub const USAGE: &str = "\
+Usage: cargo run -- [options]
+
+Options:
+  --input <path>  read the puzzle input from <path>, or stdin for `-`
+                  (default: ../input.txt)
+  --example <n>   read the input from ../example_<n>.txt
+  --part <1|2>    only run one part
+  --time          print how long each part took
+  --format <fmt>  print answers as text, json (one object per line) or tsv
+                  (default: text)
+  -h, --help      print this message";
+
+#[derive(Error, Debug)]
+#[allow(clippy::module_name_repetitions)]
+pub enum CliError {
+    #[error("unknown argument {0:?}")]
+    UnknownArgument(String),
+
+    #[error("{0} needs a value")]
+    MissingValue(&'static str),
+
+    #[error("invalid part {0:?}, expected 1 or 2")]
+    InvalidPart(String),
+
+    #[error("invalid example number {0:?}")]
+    InvalidExample(String),
+
+    #[error(transparent)]
+    InvalidFormat(#[from] UnknownFormat),
+
+    #[error("input file {} doesn't exist; save the input there or pass `--input <path>`", .0.display())]
+    InputNotFound(PathBuf),
+
+    #[error("couldn't read input file {}: {source}", path.display())]
+    ReadInput { path: PathBuf, source: io::Error },
+
+    #[error("couldn't read input from stdin: {0}")]
+    ReadStdin(io::Error),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+    Example(usize),
+}
+
+impl Input {
+    /// # Errors
+    /// * `CliError::InputNotFound` - if the input file doesn't exist
+    /// * `CliError::ReadInput` - if the input file can't be read
+    /// * `CliError::ReadStdin` - if stdin can't be read
+    pub fn read(&self) -> Result<String, CliError> {
+        let path = match self {
+            Input::File(path) => path.clone(),
+            Input::Example(n) => PathBuf::from(format!("../example_{n}.txt")),
+            Input::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(CliError::ReadStdin)?;
+
+                return Ok(input);
+            }
+        };
+
+        fs::read_to_string(&path).map_err(|source| match source.kind() {
+            io::ErrorKind::NotFound => CliError::InputNotFound(path),
+            _ => CliError::ReadInput { path, source },
+        })
+    }
+}
+
+/// The options a day's binary was run with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub input: Input,
+    pub part: Option<u8>,
+    pub time: bool,
+    pub format: Format,
+    pub help: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            input: Input::File(PathBuf::from(DEFAULT_INPUT)),
+            part: None,
+            time: false,
+            format: Format::Text,
+            help: false,
+        }
+    }
+}
+
+impl Options {
+    /// Parses the arguments after the program name
+    ///
+    /// # Errors
+    /// * `CliError::UnknownArgument` - if an argument isn't one of the options
+    /// * `CliError::MissingValue` - if an option is missing its value
+    /// * `CliError::InvalidPart` - if the part isn't 1 or 2
+    /// * `CliError::InvalidExample` - if the example isn't a number
+    /// * `CliError::InvalidFormat` - if the format isn't text, json or tsv
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options, CliError> {
+        let mut options = Options::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--input" => {
+                    let path = args.next().ok_or(CliError::MissingValue("--input"))?;
+
+                    options.input = if path == "-" {
+                        Input::Stdin
+                    } else {
+                        Input::File(PathBuf::from(path))
+                    };
+                }
+                "--example" => {
+                    let n = args.next().ok_or(CliError::MissingValue("--example"))?;
+
+                    options.input =
+                        Input::Example(n.parse().map_err(|_| CliError::InvalidExample(n))?);
+                }
+                "--part" => {
+                    let part = args.next().ok_or(CliError::MissingValue("--part"))?;
+
+                    options.part = match part.as_str() {
+                        "1" => Some(1),
+                        "2" => Some(2),
+                        _ => return Err(CliError::InvalidPart(part)),
+                    };
+                }
+                "--time" => options.time = true,
+                "--format" => {
+                    let format = args.next().ok_or(CliError::MissingValue("--format"))?;
+
+                    options.format = format.parse()?;
+                }
+                "-h" | "--help" => options.help = true,
+                _ => return Err(CliError::UnknownArgument(arg)),
+            }
+        }
+
+        Ok(options)
+    }
+
+    #[must_use]
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|only| only == part)
+    }
+}
+
+/// The entry point for a day's binary whose parts parse the input themselves,
+/// which runs them as the command-line arguments ask.
+///
+/// Exits with 1 if the input can't be read or a part fails, and 2 if the
+/// arguments are invalid.
+pub fn main<P1, P2, E1, E2>(part_1: P1, part_2: P2) -> ExitCode
+where
+    P1: FnOnce(&str) -> Result<String, E1>,
+    P2: FnOnce(&str) -> Result<String, E2>,
+    E1: Locate,
+    E2: Locate,
+{
+    Runner::from_env().run(part_1, part_2)
+}
+
+/// The options and input for a run of a day's binary.
+///
+/// Days that parse the input once for both parts use
+/// `Runner::from_env().solve(shared::parse, part_1::run, part_2::run)`, since
+/// the parsed input can borrow from the input the runner holds.
+#[derive(Debug)]
+pub struct Runner {
+    // Or the exit code to give once the arguments or input turned out invalid
+    setup: Result<Setup, ExitCode>,
+}
+
+#[derive(Debug)]
+struct Setup {
+    options: Options,
+    input: String,
+    /// The puzzle the binary solves, for answer records
+    year: Option<u32>,
+    day: Option<u32>,
+}
+
+impl Setup {
+    /// Runs a part, printing its answer and returning whether it succeeded.
+    /// `parse_duration` is how long parsing the input took, if it was parsed
+    /// once for both parts.
+    fn run_part<P, E>(&self, part_number: u8, parse_duration: Option<Duration>, part: P) -> bool
+    where
+        P: FnOnce() -> Result<String, E>,
+        E: Locate,
+    {
+        let (result, elapsed) = timed(part);
+        let result = result.with_input(&self.input);
+        let succeeded = result.is_ok();
+
+        self.print(
+            part_number,
+            result.map_err(|error| error.to_string()),
+            elapsed,
+            parse_duration,
+        );
+
+        if self.options.time && self.options.format == Format::Text {
+            println!("Part {part_number} time: {elapsed:?}");
+        }
+
+        succeeded
+    }
+
+    fn print(
+        &self,
+        part: u8,
+        answer: Result<String, String>,
+        duration: Duration,
+        parse_duration: Option<Duration>,
+    ) {
+        let record = AnswerRecord {
+            year: self.year,
+            day: self.day,
+            part,
+            answer,
+            duration: Some(duration),
+            language: "rust".to_owned(),
+            parse_duration,
+        };
+
+        println!("{}", record.line(self.options.format));
+    }
+}
+
+impl Runner {
+    /// Reads the options from the command-line arguments, and then the input
+    /// they point to, printing the usage or any errors along the way
+    #[must_use]
+    pub fn from_env() -> Runner {
+        let options = match Options::parse(env::args().skip(1)) {
+            Ok(options) if options.help => {
+                println!("{USAGE}");
+                return Runner {
+                    setup: Err(ExitCode::SUCCESS),
+                };
+            }
+            Ok(options) => options,
+            Err(error) => {
+                eprintln!("error: {error}\n\n{USAGE}");
+                return Runner {
+                    setup: Err(ExitCode::from(2)),
+                };
+            }
+        };
+
+        let (year, day) = env::current_dir()
+            .map(|dir| puzzle_from_dir(&dir))
+            .unwrap_or_default();
+
+        let setup = match options.input.read() {
+            Ok(input) => Ok(Setup {
+                options,
+                input,
+                year,
+                day,
+            }),
+            Err(error) => {
+                eprintln!("error: {error}");
+                Err(ExitCode::FAILURE)
+            }
+        };
+
+        Runner { setup }
+    }
+
+    /// Runs the parts on the raw input
+    pub fn run<P1, P2, E1, E2>(&self, part_1: P1, part_2: P2) -> ExitCode
+    where
+        P1: FnOnce(&str) -> Result<String, E1>,
+        P2: FnOnce(&str) -> Result<String, E2>,
+        E1: Locate,
+        E2: Locate,
+    {
+        let setup = match &self.setup {
+            Ok(setup) => setup,
+            Err(code) => return *code,
+        };
+        let (options, input) = (&setup.options, setup.input.as_str());
+
+        let succeeded = [
+            !options.runs_part(1) || setup.run_part(1, None, || part_1(input)),
+            !options.runs_part(2) || setup.run_part(2, None, || part_2(input)),
+        ];
+
+        exit_code(succeeded)
+    }
+
+    /// Parses the input once, then runs the parts on the parsed input, which
+    /// they can take by any type it borrows as (like a slice of a `Vec`)
+    pub fn solve<'a, T, B, P, P1, P2, E, E1, E2>(
+        &'a self,
+        parse: P,
+        part_1: P1,
+        part_2: P2,
+    ) -> ExitCode
+    where
+        P: FnOnce(&'a str) -> Result<T, E>,
+        T: Borrow<B>,
+        B: ?Sized,
+        P1: FnOnce(&B) -> Result<String, E1>,
+        P2: FnOnce(&B) -> Result<String, E2>,
+        E: Locate,
+        E1: Locate,
+        E2: Locate,
+    {
+        let setup = match &self.setup {
+            Ok(setup) => setup,
+            Err(code) => return *code,
+        };
+        let (options, input) = (&setup.options, setup.input.as_str());
+        let text = options.format == Format::Text;
+
+        let (parsed, elapsed) = timed(|| parse(input));
+
+        if options.time && text {
+            println!("Parse time: {elapsed:?}");
+        }
+
+        let parsed = match parsed.with_input(input) {
+            Ok(parsed) => parsed,
+            Err(error) if text => {
+                println!("Parse error!: {error}");
+                return ExitCode::FAILURE;
+            }
+            Err(error) => {
+                // Records are per part, so each part that was meant to run
+                // fails with the parse error
+                for part in [1, 2].into_iter().filter(|&part| options.runs_part(part)) {
+                    setup.print(
+                        part,
+                        Err(format!("parse error: {error}")),
+                        Duration::ZERO,
+                        Some(elapsed),
+                    );
+                }
+
+                return ExitCode::FAILURE;
+            }
+        };
+
+        let succeeded = [
+            !options.runs_part(1) || setup.run_part(1, Some(elapsed), || part_1(parsed.borrow())),
+            !options.runs_part(2) || setup.run_part(2, Some(elapsed), || part_2(parsed.borrow())),
+        ];
+
+        exit_code(succeeded)
+    }
+}
+
+fn exit_code(succeeded: [bool; 2]) -> ExitCode {
+    if succeeded.iter().all(|&ok| ok) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// The year and day of a solution's directory, like `2023/06/rust`
+fn puzzle_from_dir(dir: &Path) -> (Option<u32>, Option<u32>) {
+    let number = |dir: Option<&Path>| {
+        dir.and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse().ok())
+    };
+
+    let day_dir = dir.parent();
+
+    (number(day_dir.and_then(Path::parent)), number(day_dir))
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let out = f();
+
+    (out, start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, CliError> {
+        Options::parse(args.iter().map(|&arg| arg.to_owned()))
+    }
+
+    macro_rules! options_test {
+        ($suite:ident, $($name:ident: $args:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(parse(&$args).unwrap(), $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    options_test!(options_tests,
+        defaults: [] => Options::default(),
+        input_file: ["--input", "big.txt"] => Options {
+            input: Input::File(PathBuf::from("big.txt")),
+            ..Options::default()
+        },
+        stdin: ["--input", "-"] => Options {
+            input: Input::Stdin,
+            ..Options::default()
+        },
+        example_part_2: ["--example", "2", "--part", "2", "--time"] => Options {
+            input: Input::Example(2),
+            part: Some(2),
+            time: true,
+            ..Options::default()
+        },
+        json: ["--format", "json"] => Options {
+            format: Format::Json,
+            ..Options::default()
+        },
+        help: ["-h"] => Options {
+            help: true,
+            ..Options::default()
+        },
+    );
+
+    #[test]
+    fn invalid_arguments() {
+        assert_eq!(
+            parse(&["--part", "3"]).unwrap_err().to_string(),
+            "invalid part \"3\", expected 1 or 2"
+        );
+        assert_eq!(
+            parse(&["--input"]).unwrap_err().to_string(),
+            "--input needs a value"
+        );
+        assert_eq!(
+            parse(&["--example", "one"]).unwrap_err().to_string(),
+            "invalid example number \"one\""
+        );
+        assert_eq!(
+            parse(&["--format", "yaml"]).unwrap_err().to_string(),
+            "unknown output format \"yaml\", expected text, json or tsv"
+        );
+        assert_eq!(
+            parse(&["input.txt"]).unwrap_err().to_string(),
+            "unknown argument \"input.txt\""
+        );
+    }
+
+    #[test]
+    fn missing_input_file() {
+        let error = Input::File(PathBuf::from("no/such/input.txt"))
+            .read()
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "input file no/such/input.txt doesn't exist; save the input there or pass `--input <path>`"
+        );
+    }
+
+    #[test]
+    fn puzzle_from_dir() {
+        assert_eq!(
+            super::puzzle_from_dir(Path::new("/src/aoc/2023/06/rust")),
+            (Some(2023), Some(6))
+        );
+        assert_eq!(super::puzzle_from_dir(Path::new("/tmp/rust")), (None, None));
+    }
+
+    #[test]
+    fn runs_part() {
+        let only_2 = parse(&["--part", "2"]).unwrap();
+
+        assert!(!only_2.runs_part(1));
+        assert!(only_2.runs_part(2));
+        assert!(Options::default().runs_part(1));
+    }
+}
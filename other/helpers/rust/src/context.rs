@@ -0,0 +1,311 @@
+use std::{error, fmt};
+
+use crate::{parse::Span, Error, ParseErrorKind};
+
+/// Where in the puzzle input an error happened.
+///
+/// Lines and columns are 1-based, and columns and lengths count characters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub length: usize,
+}
+
+impl Location {
+    #[must_use]
+    pub fn new(line: usize, column: usize) -> Location {
+        Location {
+            line,
+            column,
+            length: 1,
+        }
+    }
+
+    /// The whole of line `line`
+    #[must_use]
+    pub fn line(line: usize) -> Location {
+        Location {
+            line,
+            column: 1,
+            length: 0,
+        }
+    }
+
+    /// The cell at `(x, y)` in a map parsed from the whole input
+    #[must_use]
+    pub fn cell(x: usize, y: usize) -> Location {
+        Location::new(y + 1, x + 1)
+    }
+}
+
+impl From<Span<'_>> for Location {
+    fn from(span: Span<'_>) -> Self {
+        Location {
+            line: span.line,
+            column: span.column,
+            length: span.text.chars().count().max(1),
+        }
+    }
+}
+
+/// An error that may know where in the input it happened.
+///
+/// The default implementation looks through the error's sources for an
+/// [`Error`] from this crate, so a day's error enum that wraps it with
+/// `#[from]` only needs an empty `impl Locate for Error {}`.
+pub trait Locate: error::Error + 'static {
+    fn location(&self) -> Option<Location> {
+        self.source().and_then(locate)
+    }
+}
+
+impl Locate for Error {
+    fn location(&self) -> Option<Location> {
+        Some(match self {
+            Error::InconsistentMapWidth {
+                line,
+                expected,
+                found,
+            } => Location::new(*line, expected.min(found) + 1),
+            Error::InvalidCell { line, column, .. } => Location::new(*line, *column),
+            Error::Parse { line, column, kind } => Location {
+                line: *line,
+                column: *column,
+                length: match kind {
+                    ParseErrorKind::Expected(_) => 1,
+                    ParseErrorKind::InvalidValue { text, .. } => text.chars().count().max(1),
+                },
+            },
+        })
+    }
+}
+
+/// Finds the location of `error` or the first of its sources that's an
+/// [`Error`] from this crate
+#[must_use]
+pub fn locate(error: &(dyn error::Error + 'static)) -> Option<Location> {
+    let mut error = Some(error);
+
+    while let Some(e) = error {
+        if let Some(e) = e.downcast_ref::<Error>() {
+            return e.location();
+        }
+
+        error = e.source();
+    }
+
+    None
+}
+
+/// An error along with its location and the lines of input around it, which
+/// are displayed as a caret-underlined excerpt.
+#[derive(Debug, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct ErrorContext<E> {
+    pub error: E,
+    pub location: Option<Location>,
+    snippet: Vec<(usize, String)>,
+}
+
+impl<E> ErrorContext<E> {
+    /// Attaches `location`, replacing any location found in the error itself
+    #[must_use]
+    pub fn at(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self.snippet.clear();
+        self
+    }
+
+    /// Copies the lines around the error's location out of `input`
+    #[must_use]
+    pub fn with_input(mut self, input: &str) -> Self {
+        self.snippet.clear();
+
+        if let Some(location) = self.location {
+            let first = location.line.saturating_sub(1).max(1);
+
+            self.snippet = input
+                .lines()
+                .enumerate()
+                .map(|(i, line)| (i + 1, line))
+                .skip(first - 1)
+                .take(location.line + 2 - first)
+                .map(|(number, line)| (number, line.to_owned()))
+                .collect();
+        }
+
+        self
+    }
+}
+
+impl<E> From<E> for ErrorContext<E>
+where
+    E: Locate,
+{
+    fn from(error: E) -> Self {
+        ErrorContext {
+            location: error.location(),
+            error,
+            snippet: Vec::new(),
+        }
+    }
+}
+
+impl<E> fmt::Display for ErrorContext<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+
+        let Some(location) = self.location else {
+            return Ok(());
+        };
+
+        let gutter = self
+            .snippet
+            .last()
+            .map_or(location.line, |&(number, _)| number)
+            .to_string()
+            .len();
+
+        write!(
+            f,
+            "\n{:gutter$}--> line {}, column {}",
+            "", location.line, location.column
+        )?;
+
+        if self.snippet.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, "\n{:gutter$} |", "")?;
+
+        for (number, line) in &self.snippet {
+            write!(f, "\n{number:>gutter$} | {line}")?;
+
+            if *number == location.line {
+                write!(f, "\n{:gutter$} | ", "")?;
+
+                // Keep tabs so the carets line up with the excerpt above
+                for c in line.chars().take(location.column - 1) {
+                    f.write_str(if c == '\t' { "\t" } else { " " })?;
+                }
+
+                let length = if location.length == 0 {
+                    line.chars().count().saturating_sub(location.column - 1)
+                } else {
+                    location.length
+                };
+
+                write!(f, "{}", "^".repeat(length.max(1)))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<E> error::Error for ErrorContext<E>
+where
+    E: error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Adds input context to the error of a solution's result
+pub trait WithInput<T, E> {
+    /// # Errors
+    /// The original error, along with its location and surrounding input
+    fn with_input(self, input: &str) -> Result<T, ErrorContext<E>>;
+}
+
+impl<T, E> WithInput<T, E> for Result<T, E>
+where
+    E: Locate,
+{
+    fn with_input(self, input: &str) -> Result<T, ErrorContext<E>> {
+        self.map_err(|error| ErrorContext::from(error).with_input(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[derive(thiserror::Error, Debug)]
+    enum DayError {
+        #[error("{0}")]
+        AocHelper(#[from] Error),
+
+        #[error("no start found")]
+        NoStart,
+    }
+
+    impl Locate for DayError {}
+
+    #[test]
+    fn locate_through_day_error() {
+        let error = DayError::from(Error::InvalidCell {
+            line: 3,
+            column: 2,
+            byte: b'?',
+            reason: "unknown cell".to_owned(),
+        });
+
+        assert_eq!(error.location(), Some(Location::new(3, 2)));
+        assert_eq!(DayError::NoStart.location(), None);
+    }
+
+    #[test]
+    fn excerpt() {
+        let input = "Game 1: 4 red\nGame two: 1 blue\nGame 3: 2 green\nGame 4: 5 red\n";
+        let error = parse::lines(input)
+            .map(|line| {
+                line.split_once(":")?
+                    .0
+                    .words()
+                    .nth(1)
+                    .unwrap()
+                    .parse::<u32>()
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(DayError::from)
+            .with_input(input)
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            [
+                "parse error on line 2, column 6: invalid value \"two\": invalid digit found in string",
+                " --> line 2, column 6",
+                "  |",
+                "1 | Game 1: 4 red",
+                "2 | Game two: 1 blue",
+                "  |      ^^^",
+                "3 | Game 3: 2 green",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn excerpt_keeps_tabs() {
+        let error = ErrorContext::from(DayError::NoStart)
+            .at(Location::new(1, 3))
+            .with_input("\t\tS");
+
+        assert!(error.to_string().ends_with("1 | \t\tS\n  | \t\t^"));
+    }
+
+    #[test]
+    fn no_location() {
+        let error = ErrorContext::from(DayError::NoStart).with_input("...");
+
+        assert_eq!(error.to_string(), "no start found");
+    }
+}
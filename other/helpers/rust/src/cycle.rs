@@ -0,0 +1,232 @@
+use std::hash::Hash;
+
+use crate::hash::FastHashMap;
+
+/// Where a sequence of states starts repeating, and how often it repeats.
+///
+/// States `start..start + length` form the cycle, so state `start + length`
+/// is the same as state `start`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Maps any step number to the earliest step with the same state
+    #[must_use]
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle of `step` starting from `initial` using Brent's algorithm,
+/// which only keeps two states in memory at a time.
+///
+/// Never returns if the states don't eventually repeat.
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // Find the cycle length by moving the tortoise up to the hare at every
+    // power of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // Then find the start by walking two states `length` apart until they meet
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds the cycle of `step` starting from `initial` by remembering every
+/// state seen, returning the states up to the end of the first cycle.
+///
+/// This calls `step` fewer times than [`find_cycle`], so it's faster when
+/// steps are expensive and states are cheap to hash.
+///
+/// Never returns if the states don't eventually repeat.
+pub fn find_cycle_hashed<S, F>(initial: S, mut step: F) -> (Cycle, Vec<S>)
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = FastHashMap::default();
+    let mut history = Vec::new();
+    let mut state = initial;
+
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: history.len() - start,
+            };
+
+            return (cycle, history);
+        }
+
+        seen.insert(state.clone(), history.len());
+
+        let next = step(&state);
+        history.push(state);
+        state = next;
+    }
+}
+
+/// Returns the state after `n` steps from `initial`, skipping ahead once the
+/// states start repeating.
+pub fn nth_state<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = FastHashMap::default();
+    let mut history = Vec::new();
+    let mut state = initial;
+
+    while history.len() < n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: history.len() - start,
+            };
+
+            return history.swap_remove(cycle.equivalent_step(n));
+        }
+
+        seen.insert(state.clone(), history.len());
+
+        let next = step(&state);
+        history.push(state);
+        state = next;
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        map::Map2D,
+        neighbors::{Grid2D, Grid2DMut},
+    };
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 7 -> 3
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn tail_step(&x: &u32) -> u32 {
+        if x == 7 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    macro_rules! find_cycle_test {
+        ($suite:ident, $($name:ident: $initial:expr, $step:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(find_cycle($initial, $step), $expected);
+                        assert_eq!(find_cycle_hashed($initial, $step).0, $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    find_cycle_test!(find_cycle_tests,
+        fixed_point: 0_u32, |&x: &u32| x => Cycle { start: 0, length: 1 },
+        pure_cycle: 0_u32, |&x: &u32| (x + 1) % 5 => Cycle { start: 0, length: 5 },
+        with_tail: 0_u32, tail_step => Cycle { start: 3, length: 5 },
+        start_inside_cycle: 5_u32, tail_step => Cycle { start: 0, length: 5 },
+    );
+
+    #[test]
+    fn equivalent_step() {
+        let cycle = Cycle {
+            start: 3,
+            length: 5,
+        };
+
+        assert_eq!(cycle.equivalent_step(2), 2);
+        assert_eq!(cycle.equivalent_step(8), 3);
+        assert_eq!(cycle.equivalent_step(1_000_000_000), 5);
+    }
+
+    #[test]
+    fn nth_state_skips_ahead() {
+        assert_eq!(nth_state(0, tail_step, 0), 0);
+        assert_eq!(nth_state(0, tail_step, 7), 7);
+        assert_eq!(nth_state(0, tail_step, 1_000_000_000), 5);
+    }
+
+    #[test]
+    fn grid_states() {
+        // Rotates a single lit cell clockwise around a 2x2 grid
+        let step = |map: &Map2D<bool>| {
+            let mut next = Map2D::new_parallel(map, false);
+
+            for (x, y, &lit) in map.iter() {
+                if lit {
+                    let (x, y) = match (x, y) {
+                        (0, 0) => (1, 0),
+                        (1, 0) => (1, 1),
+                        (1, 1) => (0, 1),
+                        _ => (0, 0),
+                    };
+
+                    next.set(x, y, true);
+                }
+            }
+
+            next
+        };
+
+        let mut initial = Map2D::new(2, 2, false);
+        initial.set(0, 0, true);
+
+        assert_eq!(
+            find_cycle_hashed(initial.clone(), step).0,
+            Cycle {
+                start: 0,
+                length: 4
+            }
+        );
+        assert_eq!(nth_state(initial, step, 1_000_000_001).get(1, 0), Some(&true));
+    }
+}
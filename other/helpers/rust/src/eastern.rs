@@ -0,0 +1,160 @@
+use std::ops::Range;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+
+/// Days since 1970-01-01 for a date in the proleptic Gregorian calendar
+#[must_use]
+pub fn days_from_civil(year: u32, month: u32, day: u32) -> u64 {
+    let year = u64::from(if month <= 2 { year - 1 } else { year });
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let month = u64::from(month);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + u64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The `(year, month, day)` that's `days` after 1970-01-01
+///
+/// # Panics
+/// If the year doesn't fit in a `u32`
+#[must_use]
+pub fn civil_from_days(days: u64) -> (u32, u32, u32) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+
+    (
+        u32::try_from(year).unwrap(),
+        u32::try_from(month).unwrap(),
+        u32::try_from(day).unwrap(),
+    )
+}
+
+/// When daylight saving time is in effect during `year`, as UTC timestamps.
+/// Under the rules in place since 2007, it starts at 2am on the second Sunday
+/// in March and ends at 2am on the first Sunday in November.
+#[must_use]
+pub fn dst_range(year: u32) -> Range<u64> {
+    let start = nth_sunday(year, 3, 2) * DAY + 2 * HOUR + 5 * HOUR;
+    let end = nth_sunday(year, 11, 1) * DAY + 2 * HOUR + 4 * HOUR;
+
+    start..end
+}
+
+/// How far New York is behind UTC at `timestamp`, in seconds
+#[must_use]
+pub fn utc_offset(timestamp: u64) -> u64 {
+    let (year, _, _) = civil_from_days(timestamp / DAY);
+
+    if dst_range(year).contains(&timestamp) {
+        4 * HOUR
+    } else {
+        5 * HOUR
+    }
+}
+
+/// The UTC timestamp of a wall-clock time in New York, `seconds` after
+/// midnight. Times that happen twice when the clocks go back are the first
+/// one, and times skipped when they go forward are read as standard time.
+#[must_use]
+pub fn from_local(year: u32, month: u32, day: u32, seconds: u64) -> u64 {
+    let local = days_from_civil(year, month, day) * DAY + seconds;
+    let daylight = local + 4 * HOUR;
+
+    if dst_range(year).contains(&daylight) {
+        daylight
+    } else {
+        local + 5 * HOUR
+    }
+}
+
+/// When `day`'s puzzle in `year` unlocks, at midnight in New York
+#[must_use]
+pub fn unlock_timestamp(year: u32, day: u32) -> u64 {
+    from_local(year, 12, day, 0)
+}
+
+/// The day of the `n`th Sunday in `month`, in days since 1970-01-01
+fn nth_sunday(year: u32, month: u32, n: u64) -> u64 {
+    let first = days_from_civil(year, month, 1);
+    // 1970-01-01 was a Thursday, 4 days after a Sunday
+    let weekday = (first + 4) % 7;
+
+    first + (7 - weekday) % 7 + 7 * (n - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! from_local_test {
+        ($suite:ident, $($name:ident: $local:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        let (year, month, day, hour, minute) = $local;
+                        assert_eq!(
+                            from_local(year, month, day, hour * HOUR + minute * 60),
+                            $expected
+                        );
+                    }
+                )*
+            }
+        }
+    }
+
+    from_local_test!(from_local_tests,
+        winter: (2023, 12, 1, 0, 0) => 1_701_406_800,
+        summer: (2024, 7, 4, 0, 0) => 1_720_065_600,
+        before_spring_forward: (2024, 3, 10, 1, 59) => 1_710_053_940,
+        skipped_by_spring_forward: (2024, 3, 10, 2, 30) => 1_710_055_800,
+        after_spring_forward: (2024, 3, 10, 3, 0) => 1_710_054_000,
+        repeated_by_fall_back: (2024, 11, 3, 1, 30) => 1_730_611_800,
+        after_fall_back: (2024, 11, 3, 2, 0) => 1_730_617_200,
+    );
+
+    #[test]
+    fn dst_ranges() {
+        assert_eq!(dst_range(2024), 1_710_054_000..1_730_613_600);
+
+        assert_eq!(utc_offset(1_710_054_000 - 1), 5 * HOUR);
+        assert_eq!(utc_offset(1_710_054_000), 4 * HOUR);
+        assert_eq!(utc_offset(1_730_613_600 - 1), 4 * HOUR);
+        assert_eq!(utc_offset(1_730_613_600), 5 * HOUR);
+    }
+
+    #[test]
+    fn unlocks() {
+        assert_eq!(unlock_timestamp(2015, 1), 1_448_946_000);
+        assert_eq!(unlock_timestamp(2023, 1), 1_701_406_800);
+        assert_eq!(unlock_timestamp(2023, 25), 1_701_406_800 + 24 * DAY);
+    }
+
+    #[test]
+    fn civil_dates_round_trip() {
+        assert_eq!(days_from_civil(2000, 2, 29), 951_782_400 / DAY);
+        assert_eq!(days_from_civil(2100, 3, 1), 4_107_542_400 / DAY);
+
+        for days in (0..100_000).step_by(7) {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+}
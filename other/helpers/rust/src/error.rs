@@ -2,6 +2,34 @@ use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum Error {
-    #[error("inconsistent text map width")]
-    InconsistentMapWidth,
+    #[error("inconsistent text map width on line {line}: expected {expected}, found {found}")]
+    InconsistentMapWidth {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("invalid cell {:?} on line {line}, column {column}: {reason}", char::from(*.byte))]
+    InvalidCell {
+        line: usize,
+        column: usize,
+        byte: u8,
+        reason: String,
+    },
+
+    #[error("parse error on line {line}, column {column}: {kind}")]
+    Parse {
+        line: usize,
+        column: usize,
+        kind: ParseErrorKind,
+    },
+}
+
+#[derive(Error, Debug, PartialEq, Eq, Clone)]
+pub enum ParseErrorKind {
+    #[error("expected {0:?}")]
+    Expected(String),
+
+    #[error("invalid value {text:?}: {reason}")]
+    InvalidValue { text: String, reason: String },
 }
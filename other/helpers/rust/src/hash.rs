@@ -0,0 +1,131 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasherDefault, Hash, Hasher},
+};
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+/// The non-cryptographic hash from Firefox and rustc, which is much faster
+/// than the standard library's `SipHash` for small keys like integers and
+/// short strings.
+///
+/// It has no random state, so hashes (and the iteration order of maps using
+/// it) are the same on every run. That also means it's easy to find
+/// collisions for, so it shouldn't see untrusted input.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+impl FxHasher {
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+
+        let mut rest = chunks.remainder();
+
+        if rest.len() >= 4 {
+            self.add(u64::from(u32::from_le_bytes(rest[..4].try_into().unwrap())));
+            rest = &rest[4..];
+        }
+
+        for &byte in rest {
+            self.add(u64::from(byte));
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add(u64::from(i));
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.add(u64::from(i));
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add(u64::from(i));
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        // Hash as 64 bits everywhere, so hashes don't depend on the platform
+        self.add(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+
+/// A `HashMap` using [`FxHasher`], created with `FastHashMap::default()`
+pub type FastHashMap<K, V> = HashMap<K, V, FxBuildHasher>;
+
+/// A `HashSet` using [`FxHasher`], created with `FastHashSet::default()`
+pub type FastHashSet<T> = HashSet<T, FxBuildHasher>;
+
+/// Hashes every item of `slice` together with [`FxHasher`]
+#[must_use]
+pub fn hash_slice<T: Hash>(slice: &[T]) -> u64 {
+    let mut hasher = FxHasher::default();
+    slice.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        // Pinned so a change to the hash (and so to map iteration orders)
+        // doesn't go unnoticed
+        assert_eq!(hash_slice::<u8>(&[]), 0);
+        assert_eq!(
+            hash_slice(&[1_u64]),
+            (SEED.rotate_left(5) ^ 1).wrapping_mul(SEED)
+        );
+    }
+
+    #[test]
+    fn distinguishes_slices() {
+        assert_ne!(hash_slice(b"#.##..##."), hash_slice(b"..#.##.#."));
+        assert_ne!(hash_slice(&[0_u8]), hash_slice(&[0_u8, 0]));
+        assert_ne!(hash_slice(&[1_u32, 2]), hash_slice(&[2_u32, 1]));
+    }
+
+    #[test]
+    fn long_writes_use_every_byte() {
+        let mut a = FxHasher::default();
+        let mut b = FxHasher::default();
+
+        a.write(b"0123456789abcdef_x");
+        b.write(b"0123456789abcdef_y");
+
+        assert_ne!(a.finish(), b.finish());
+    }
+
+    #[test]
+    fn collections() {
+        let mut map = FastHashMap::default();
+        map.insert("AAA", 1);
+
+        let set = [3, 1, 3].into_iter().collect::<FastHashSet<_>>();
+
+        assert_eq!(map.get("AAA"), Some(&1));
+        assert_eq!(set.len(), 2);
+    }
+}
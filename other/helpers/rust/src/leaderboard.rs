@@ -0,0 +1,577 @@
+use std::cmp::Reverse;
+
+use crate::{eastern, parse::Span, Error, ParseErrorKind};
+
+/// A star a member earned, when they earned it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Star {
+    pub day: u32,
+    pub part: u8,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    /// Breaks ties between stars earned in the same second
+    pub index: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous users
+    pub name: Option<String>,
+    /// The local score the site worked out
+    pub local_score: u64,
+    /// Sorted by when they were earned
+    pub stars: Vec<Star>,
+}
+
+impl Member {
+    /// The member's name, or how the site shows anonymous users
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    #[must_use]
+    pub fn star(&self, day: u32, part: u8) -> Option<&Star> {
+        self.stars
+            .iter()
+            .find(|star| star.day == day && star.part == part)
+    }
+
+    /// Seconds between the member's part 1 and part 2 stars for `day`
+    #[must_use]
+    pub fn delta(&self, day: u32) -> Option<u64> {
+        let part_1 = self.star(day, 1)?;
+        let part_2 = self.star(day, 2)?;
+
+        Some(part_2.timestamp.saturating_sub(part_1.timestamp))
+    }
+}
+
+/// A member's standing on one day
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DayRank<'a> {
+    pub member: &'a Member,
+    /// The local score points earned from that day's two parts
+    pub points: u64,
+}
+
+/// A private leaderboard, as served at
+/// `/{year}/leaderboard/private/view/{id}.json`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub year: u32,
+    pub owner_id: u64,
+    /// Sorted by id
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    /// # Errors
+    /// * `Error::Parse` - if the JSON is invalid or isn't a leaderboard
+    pub fn parse(json: &str) -> Result<Leaderboard, Error> {
+        let root = Parser::new(json).document()?;
+        let invalid = |reason: &str| {
+            Span::new(json).error(ParseErrorKind::InvalidValue {
+                text: "leaderboard".to_owned(),
+                reason: reason.to_owned(),
+            })
+        };
+
+        let year = root
+            .get("event")
+            .and_then(Json::as_str)
+            .and_then(|event| event.parse().ok())
+            .ok_or_else(|| invalid("no event year"))?;
+        let owner_id = root
+            .get("owner_id")
+            .and_then(Json::as_u64)
+            .ok_or_else(|| invalid("no owner id"))?;
+        let Some(Json::Object(entries)) = root.get("members") else {
+            return Err(invalid("no members"));
+        };
+
+        let mut members = Vec::new();
+
+        for (_, member) in entries {
+            let id = member
+                .get("id")
+                .and_then(Json::as_u64)
+                .ok_or_else(|| invalid("a member has no id"))?;
+            let mut stars = Vec::new();
+
+            if let Some(Json::Object(days)) = member.get("completion_day_level") {
+                for (day, parts) in days {
+                    let Json::Object(parts) = parts else {
+                        continue;
+                    };
+
+                    for (part, star) in parts {
+                        let (Ok(day), Ok(part), Some(timestamp)) = (
+                            day.parse(),
+                            part.parse(),
+                            star.get("get_star_ts").and_then(Json::as_u64),
+                        ) else {
+                            return Err(invalid(&format!("member {id} has an invalid star")));
+                        };
+
+                        stars.push(Star {
+                            day,
+                            part,
+                            timestamp,
+                            index: star.get("star_index").and_then(Json::as_u64).unwrap_or(0),
+                        });
+                    }
+                }
+            }
+
+            stars.sort_by_key(|star| (star.timestamp, star.index));
+
+            members.push(Member {
+                id,
+                name: member.get("name").and_then(Json::as_str).map(str::to_owned),
+                local_score: member
+                    .get("local_score")
+                    .and_then(Json::as_u64)
+                    .unwrap_or(0),
+                stars,
+            });
+        }
+
+        members.sort_by_key(|member| member.id);
+
+        Ok(Leaderboard {
+            year,
+            owner_id,
+            members,
+        })
+    }
+
+    /// When `day`'s puzzle unlocked, at midnight in New York
+    #[must_use]
+    pub fn unlock_timestamp(&self, day: u32) -> u64 {
+        eastern::unlock_timestamp(self.year, day)
+    }
+
+    /// Every day someone earned a star on, in order
+    #[must_use]
+    pub fn days(&self) -> Vec<u32> {
+        let mut days = self
+            .members
+            .iter()
+            .flat_map(|member| member.stars.iter().map(|star| star.day))
+            .collect::<Vec<_>>();
+
+        days.sort_unstable();
+        days.dedup();
+        days
+    }
+
+    /// Each member's local score, in the same order as `members`, worked out
+    /// from their stars. The first member to get a star earns as many points
+    /// as there are members, the second one fewer, and so on.
+    #[must_use]
+    pub fn local_scores(&self) -> Vec<u64> {
+        let mut scores = vec![0; self.members.len()];
+
+        for day in self.days() {
+            for (i, points) in self.day_points(day).into_iter().enumerate() {
+                scores[i] += points;
+            }
+        }
+
+        scores
+    }
+
+    /// The members who got a star on `day`, best first: by points, then by
+    /// when they got part 2 and part 1
+    #[must_use]
+    pub fn day_ranking(&self, day: u32) -> Vec<DayRank<'_>> {
+        let mut ranking = self
+            .members
+            .iter()
+            .zip(self.day_points(day))
+            .filter(|(member, _)| member.star(day, 1).is_some())
+            .map(|(member, points)| DayRank { member, points })
+            .collect::<Vec<_>>();
+
+        ranking.sort_by_key(|rank| {
+            let time = |part| rank.member.star(day, part).map(|star| star.timestamp);
+
+            (
+                Reverse(rank.points),
+                time(2).unwrap_or(u64::MAX),
+                time(1),
+                rank.member.id,
+            )
+        });
+
+        ranking
+    }
+
+    /// The points each member earned on `day`, in the same order as `members`
+    fn day_points(&self, day: u32) -> Vec<u64> {
+        let mut points = vec![0; self.members.len()];
+        let max_points = self.members.len() as u64;
+
+        for part in [1, 2] {
+            let mut finishers = self
+                .members
+                .iter()
+                .enumerate()
+                .filter_map(|(i, member)| Some((member.star(day, part)?, i)))
+                .collect::<Vec<_>>();
+
+            finishers.sort_by_key(|(star, _)| (star.timestamp, star.index));
+
+            for (rank, (_, i)) in (0..).zip(finishers) {
+                points[i] += max_points - rank;
+            }
+        }
+
+        points
+    }
+}
+
+/// Just enough JSON for leaderboards
+#[derive(Clone, Debug, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries
+                .iter()
+                .find_map(|(name, value)| (name == key).then_some(value)),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn as_u64(&self) -> Option<u64> {
+        match *self {
+            // Timestamps and ids fit well within an f64's exact integers
+            Json::Number(n) if n >= 0.0 && n.fract() == 0.0 => Some(n as u64),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: Span<'a>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(json: &'a str) -> Parser<'a> {
+        Parser {
+            input: Span::new(json),
+            position: 0,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input.text[self.position..]
+    }
+
+    fn expected(&self, what: &str) -> Error {
+        self.input
+            .slice(self.position, self.position)
+            .error(ParseErrorKind::Expected(what.to_owned()))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+
+        if self.rest().starts_with(token) {
+            self.position += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), Error> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.expected(token))
+        }
+    }
+
+    fn document(&mut self) -> Result<Json, Error> {
+        let value = self.value()?;
+        self.skip_whitespace();
+
+        if self.rest().is_empty() {
+            Ok(value)
+        } else {
+            Err(self.expected("end of JSON"))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, Error> {
+        self.skip_whitespace();
+
+        for (literal, value) in [
+            ("null", Json::Null),
+            ("true", Json::Bool(true)),
+            ("false", Json::Bool(false)),
+        ] {
+            if self.eat(literal) {
+                return Ok(value);
+            }
+        }
+
+        match self.rest().chars().next() {
+            Some('"') => Ok(Json::String(self.string()?)),
+            Some('[') => {
+                self.position += 1;
+                let mut items = Vec::new();
+
+                if !self.eat("]") {
+                    loop {
+                        items.push(self.value()?);
+
+                        if self.eat("]") {
+                            break;
+                        }
+
+                        self.expect(",")?;
+                    }
+                }
+
+                Ok(Json::Array(items))
+            }
+            Some('{') => {
+                self.position += 1;
+                let mut entries = Vec::new();
+
+                if !self.eat("}") {
+                    loop {
+                        self.skip_whitespace();
+                        let key = self.string()?;
+                        self.expect(":")?;
+                        entries.push((key, self.value()?));
+
+                        if self.eat("}") {
+                            break;
+                        }
+
+                        self.expect(",")?;
+                    }
+                }
+
+                Ok(Json::Object(entries))
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let rest = self.rest();
+                let len = rest
+                    .find(|c: char| !matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))
+                    .unwrap_or(rest.len());
+                let number = rest[..len].parse().map_err(|_| self.expected("a number"))?;
+
+                self.position += len;
+                Ok(Json::Number(number))
+            }
+            _ => Err(self.expected("a JSON value")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        if !self.rest().starts_with('"') {
+            return Err(self.expected("\""));
+        }
+
+        self.position += 1;
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.position += i + 1;
+                    return Ok(out);
+                }
+                '\\' => {
+                    let escaped = match chars.next().map(|(_, c)| c) {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => {
+                            let hex = (0..4)
+                                .filter_map(|_| chars.next().map(|(_, c)| c))
+                                .collect::<String>();
+
+                            // Surrogate pairs don't come up in names, so they
+                            // become replacement characters
+                            u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .map_or(char::REPLACEMENT_CHARACTER, |code| {
+                                    char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                                })
+                        }
+                        Some(c) => c,
+                        None => break,
+                    };
+
+                    out.push(escaped);
+                }
+                c => out.push(c),
+            }
+        }
+
+        self.position = self.input.text.len();
+        Err(self.expected("\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEADERBOARD: &str = r#"{
+        "event": "2023",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "Ada", "stars": 4, "local_score": 10,
+                "last_star_ts": 1701496000, "global_score": 0,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1701407100, "star_index": 10},
+                        "2": {"get_star_ts": 1701407400, "star_index": 12}
+                    },
+                    "2": {
+                        "1": {"get_star_ts": 1701494000, "star_index": 40},
+                        "2": {"get_star_ts": 1701496000, "star_index": 44}
+                    }
+                }
+            },
+            "7": {
+                "id": 7, "name": null, "stars": 3, "local_score": 8,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1701407100, "star_index": 11},
+                        "2": {"get_star_ts": 1701407300, "star_index": 13}
+                    },
+                    "2": {
+                        "1": {"get_star_ts": 1701493900, "star_index": 39}
+                    }
+                }
+            },
+            "3": {
+                "id": 3, "name": "Gräce \"G\"", "stars": 0, "local_score": 0,
+                "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn parses_members_and_stars() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+
+        assert_eq!(leaderboard.year, 2023);
+        assert_eq!(leaderboard.owner_id, 1);
+        assert_eq!(
+            leaderboard
+                .members
+                .iter()
+                .map(Member::display_name)
+                .collect::<Vec<_>>(),
+            ["Ada", "Gräce \"G\"", "(anonymous user #7)"]
+        );
+        assert_eq!(
+            leaderboard.members[2].stars[0],
+            Star {
+                day: 1,
+                part: 1,
+                timestamp: 1_701_407_100,
+                index: 11
+            }
+        );
+        assert_eq!(leaderboard.days(), [1, 2]);
+    }
+
+    #[test]
+    fn deltas_and_unlocks() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let ada = &leaderboard.members[0];
+
+        // Midnight EST on 2023-12-01
+        assert_eq!(leaderboard.unlock_timestamp(1), 1_701_406_800);
+        assert_eq!(ada.delta(1), Some(300));
+        assert_eq!(ada.delta(2), Some(2000));
+        assert_eq!(leaderboard.members[2].delta(2), None);
+    }
+
+    #[test]
+    fn recalculates_local_scores() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+
+        // Day 1: Ada beats #7 to part 1 on the star index, #7 gets part 2
+        // first. Day 2: #7 gets part 1 first and Ada is alone on part 2.
+        assert_eq!(leaderboard.local_scores(), [3 + 2 + 2 + 3, 0, 2 + 3 + 3]);
+        assert_eq!(
+            leaderboard
+                .members
+                .iter()
+                .map(|member| member.local_score)
+                .collect::<Vec<_>>(),
+            leaderboard.local_scores()
+        );
+    }
+
+    #[test]
+    fn ranks_days() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let ranking = |day| {
+            leaderboard
+                .day_ranking(day)
+                .into_iter()
+                .map(|rank| (rank.member.id, rank.points))
+                .collect::<Vec<_>>()
+        };
+
+        // Tied on points, so the first to finish part 2 comes first
+        assert_eq!(ranking(1), [(7, 5), (1, 5)]);
+        assert_eq!(ranking(2), [(1, 5), (7, 3)]);
+    }
+
+    #[test]
+    fn invalid_json() {
+        assert_eq!(
+            Leaderboard::parse("{\"event\": \"2023\",\n  \"owner_id\": }")
+                .unwrap_err()
+                .to_string(),
+            "parse error on line 2, column 15: expected \"a JSON value\""
+        );
+        assert_eq!(
+            Leaderboard::parse("[]").unwrap_err().to_string(),
+            "parse error on line 1, column 1: invalid value \"leaderboard\": no event year"
+        );
+    }
+}
@@ -1,9 +1,23 @@
 #![deny(clippy::all, clippy::pedantic)]
 
 pub mod answer;
+pub mod bit_grid;
+pub mod cell;
+pub mod cli;
+pub mod context;
+pub mod cycle;
+pub mod eastern;
 pub mod error;
+pub mod hash;
+pub mod leaderboard;
 pub mod map;
+pub mod math;
+pub mod memo;
 pub mod neighbors;
+pub mod parse;
+pub mod piecewise;
+pub mod ranges;
+pub mod sequence;
 pub mod text_map;
 
 pub use error::*;
@@ -1,11 +1,14 @@
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
 use crate::{
-    neighbors::{Grid2D, Grid2DMut},
+    cell::CellByte,
+    neighbors::{Grid2D, Grid2DMut, Grid2DRefMut},
+    text_map::TextMap,
     Error,
 };
 
 #[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Map2D<T> {
     map: Vec<T>,
     height: usize,
@@ -31,39 +34,95 @@ impl<T> Map2D<T> {
     {
         Map2D::new(other.width(), other.height(), default_value)
     }
-}
 
-impl FromStr for Map2D<u8> {
-    type Err = Error;
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| &self.map[y * self.width..(y + 1) * self.width])
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut map = Vec::new();
-        let mut width = None;
-        let mut height = 0;
-
-        for line in s.lines() {
-            let line = line.as_bytes();
-
-            if let Some(width) = width {
-                if line.len() != width {
-                    return Err(Error::InconsistentMapWidth);
-                }
-            } else {
-                width = Some(line.len());
-            }
-
-            map.extend_from_slice(line);
-            height += 1;
+impl<T> Map2D<T> {
+    /// Parses a text map, converting each byte into a cell with `decode`
+    ///
+    /// # Errors
+    /// * `Error::InconsistentMapWidth` - map has inconsistent line length (map width)
+    /// * `Error::InvalidCell` - `decode` rejected a byte
+    pub fn parse_with<F, E>(source: &str, mut decode: F) -> Result<Map2D<T>, Error>
+    where
+        F: FnMut(u8) -> Result<T, E>,
+        E: fmt::Display,
+    {
+        let text_map = TextMap::parse(source)?;
+        let mut map = Vec::with_capacity(text_map.width() * text_map.height());
+
+        for (x, y, &byte) in text_map.iter() {
+            map.push(decode(byte).map_err(|e| Error::InvalidCell {
+                line: y + 1,
+                column: x + 1,
+                byte,
+                reason: e.to_string(),
+            })?);
         }
 
         Ok(Map2D {
             map,
-            width: width.unwrap_or(0),
-            height,
+            height: text_map.height(),
+            width: text_map.width(),
         })
     }
 }
 
+impl<T> Map2D<T>
+where
+    T: CellByte,
+{
+    /// # Errors
+    /// * `Error::InconsistentMapWidth` - map has inconsistent line length (map width)
+    /// * `Error::InvalidCell` - a byte doesn't match any cell
+    pub fn parse_cells(source: &str) -> Result<Map2D<T>, Error> {
+        Map2D::parse_with(source, |byte| T::from_byte(byte).ok_or("unknown cell"))
+    }
+}
+
+impl<T> fmt::Display for Map2D<T>
+where
+    T: CellByte,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            let row = row.iter().map(CellByte::to_byte).collect::<Vec<_>>();
+
+            f.write_str(&String::from_utf8_lossy(&row))?;
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<TextMap<'_>> for Map2D<u8> {
+    fn from(text_map: TextMap<'_>) -> Self {
+        let mut map = Vec::with_capacity(text_map.width() * text_map.height());
+
+        for row in text_map.rows() {
+            map.extend_from_slice(row);
+        }
+
+        Map2D {
+            map,
+            height: text_map.height(),
+            width: text_map.width(),
+        }
+    }
+}
+
+impl FromStr for Map2D<u8> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TextMap::parse(s).map(Map2D::from)
+    }
+}
+
 impl<T> Grid2D for Map2D<T> {
     type Item = T;
 
@@ -88,6 +147,14 @@ impl<T> Grid2D for Map2D<T> {
 }
 
 impl<T> Grid2DMut for Map2D<T> {
+    fn set(&mut self, x: usize, y: usize, item: T) -> Option<T> {
+        let space = self.get_mut(x, y)?;
+
+        Some(std::mem::replace(space, item))
+    }
+}
+
+impl<T> Grid2DRefMut for Map2D<T> {
     fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
         self.map.get_mut(x + y * self.width)
     }
@@ -0,0 +1,383 @@
+use std::ops::Range;
+
+#[must_use]
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// The least common multiple of `a` and `b`, or 0 if either is 0
+///
+/// # Panics
+/// If the result overflows `u64`
+#[must_use]
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        a / gcd(a, b) * b
+    }
+}
+
+/// The gcd of every number, or 0 if there are none
+pub fn gcd_all<I: IntoIterator<Item = u64>>(numbers: I) -> u64 {
+    numbers.into_iter().fold(0, gcd)
+}
+
+/// The lcm of every number, or 1 if there are none
+///
+/// # Panics
+/// If the result overflows `u64`
+pub fn lcm_all<I: IntoIterator<Item = u64>>(numbers: I) -> u64 {
+    numbers.into_iter().fold(1, lcm)
+}
+
+/// Returns `(g, x, y)` where `g` is the non-negative gcd of `a` and `b`, and
+/// `a * x + b * y == g`
+///
+/// # Panics
+/// If `g` doesn't fit in an `i64`, which only happens when `a` and `b` are each
+/// `i64::MIN` or 0
+#[must_use]
+#[allow(clippy::many_single_char_names)]
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_i128(a.into(), b.into());
+    let (g, x, y) = if g < 0 { (-g, -x, -y) } else { (g, x, y) };
+
+    (
+        i64::try_from(g).expect("gcd overflowed i64"),
+        i64::try_from(x).unwrap(),
+        i64::try_from(y).unwrap(),
+    )
+}
+
+/// The `x` in `0..m` where `a * x ≡ 1 (mod m)`, or `None` if `a` and `m`
+/// aren't coprime or `m` isn't positive
+#[must_use]
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base` to the power of `exp`, modulo `m`
+///
+/// # Panics
+/// If `m` is 0
+#[must_use]
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    let m = u128::from(m);
+    let mut base = u128::from(base) % m;
+    let mut result = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+
+        base = base * base % m;
+        exp >>= 1;
+    }
+
+    // Both were reduced modulo a u64
+    u64::try_from(result).unwrap()
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese
+/// Remainder Theorem, returning the smallest non-negative `x` along with the
+/// lcm of the moduli, which every other solution differs from `x` by a
+/// multiple of.
+///
+/// The moduli don't need to be coprime. Returns `None` if the congruences
+/// contradict each other, or if the lcm of the moduli overflows `i64`.
+///
+/// # Panics
+/// If any modulus isn't positive
+pub fn crt<I: IntoIterator<Item = (i64, i64)>>(congruences: I) -> Option<(i64, i64)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+
+    for (residue, modulus) in congruences {
+        assert!(modulus > 0, "crt moduli must be positive");
+
+        let residue = i128::from(residue).rem_euclid(i128::from(modulus));
+        let modulus = i128::from(modulus);
+
+        // Solve x + m * k ≡ residue (mod modulus) for k
+        let (g, inverse, _) = extended_gcd_i128(m, modulus);
+        let diff = residue - x;
+
+        if diff % g != 0 {
+            return None;
+        }
+
+        let step = modulus / g;
+        let k = (diff / g % step * inverse).rem_euclid(step);
+
+        x += m * k;
+        m *= step;
+
+        if m > i128::from(i64::MAX) {
+            return None;
+        }
+
+        x = x.rem_euclid(m);
+    }
+
+    Some((i64::try_from(x).ok()?, i64::try_from(m).ok()?))
+}
+
+#[allow(clippy::many_single_char_names)]
+fn extended_gcd_i128(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    (old_r, old_x, old_y)
+}
+
+/// The largest `r` where `r * r <= n`
+#[must_use]
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method from an overestimate decreases monotonically to the root
+    let mut x: u128 = 1 << (n.ilog2() / 2 + 1);
+
+    loop {
+        let next = x.midpoint(n / x);
+
+        if next >= x {
+            return x;
+        }
+
+        x = next;
+    }
+}
+
+/// Counts the integers `x` in `range` where `a·x² + b·x + c > 0`, without any
+/// floating point rounding at the boundaries.
+///
+/// Completing the square gives `4a·f(x) = (2a·x + b)² - (b² - 4ac)`, so the
+/// sign of `f(x)` only depends on how `|2a·x + b|` compares to the square root
+/// of the discriminant, which integer square roots answer exactly.
+///
+/// # Panics
+/// If `b² - 4ac` overflows an `i128`, which needs `|a|` and `|c|` close to
+/// `i64::MAX`
+#[must_use]
+pub fn count_quadratic_positive(a: i64, b: i64, c: i64, range: Range<i64>) -> u64 {
+    let (a, b, c) = (i128::from(a), i128::from(b), i128::from(c));
+    let (lo, hi) = (i128::from(range.start), i128::from(range.end) - 1);
+
+    let count = |from: i128, to: i128| {
+        let (from, to) = (from.max(lo), to.min(hi));
+
+        u64::try_from((to - from + 1).max(0)).unwrap()
+    };
+    let all = count(lo, hi);
+
+    if a == 0 {
+        // b·x + c > 0
+        return match b.signum() {
+            0 if c > 0 => all,
+            0 => 0,
+            1 => count(floor_div(-c, b) + 1, hi),
+            _ => count(lo, ceil_div(c, -b) - 1),
+        };
+    }
+
+    let discriminant = b
+        .checked_mul(b)
+        .and_then(|b2| b2.checked_sub(a.checked_mul(c)?.checked_mul(4)?))
+        .expect("quadratic discriminant overflowed i128");
+
+    // Counts the x where |2a·x + b| <= bound
+    let within = |bound: i128| {
+        let (from, to) = (-bound - b, bound - b);
+
+        if a > 0 {
+            count(ceil_div(from, 2 * a), floor_div(to, 2 * a))
+        } else {
+            count(ceil_div(to, 2 * a), floor_div(from, 2 * a))
+        }
+    };
+
+    if a > 0 {
+        // f(x) > 0 where (2a·x + b)² > discriminant
+        if discriminant < 0 {
+            all
+        } else {
+            all - within(isqrt_i128(discriminant))
+        }
+    } else if discriminant <= 0 {
+        // f(x) > 0 where (2a·x + b)² < discriminant, which never happens
+        0
+    } else {
+        within(isqrt_i128(discriminant - 1))
+    }
+}
+
+fn isqrt_i128(n: i128) -> i128 {
+    i128::try_from(isqrt(n.unsigned_abs())).unwrap()
+}
+
+fn floor_div(a: i128, b: i128) -> i128 {
+    let q = a / b;
+
+    if a % b != 0 && (a < 0) != (b < 0) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+fn ceil_div(a: i128, b: i128) -> i128 {
+    -floor_div(-a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    macro_rules! crt_test {
+        ($suite:ident, $($name:ident: $congruences:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(crt($congruences), $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    crt_test!(crt_tests,
+        empty: [] => Some((0, 1)),
+        single: [(7, 5)] => Some((2, 5)),
+        coprime: [(2, 3), (3, 5), (2, 7)] => Some((23, 105)),
+        negative_residue: [(-1, 4), (0, 3)] => Some((3, 12)),
+        non_coprime: [(3, 4), (5, 6)] => Some((11, 12)),
+        non_coprime_conflict: [(1, 4), (2, 6)] => None,
+        same_modulus: [(4, 10), (14, 10)] => Some((4, 10)),
+        ghost_cycles: [(1, 2), (2, 3)] => Some((5, 6)),
+        overflow: [(0, i64::MAX), (1, i64::MAX - 1)] => None,
+    );
+
+    #[test]
+    fn gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        assert_eq!(lcm_all([2, 3, 4]), 12);
+        assert_eq!(lcm_all([]), 1);
+    }
+
+    #[test]
+    fn modular() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(-4, 6), (2, 1, 1));
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 6), None);
+        assert_eq!(mod_inverse(3, 0), None);
+        assert_eq!(mod_inverse(3, -11), None);
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(
+            mod_pow(u64::MAX, u64::MAX, u64::MAX - 58),
+            mod_pow(58, u64::MAX, u64::MAX - 58)
+        );
+        assert_eq!(mod_pow(5, 0, 1), 0);
+    }
+
+    macro_rules! quadratic_test {
+        ($suite:ident, $($name:ident: $a:expr, $b:expr, $c:expr, $range:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(count_quadratic_positive($a, $b, $c, $range), $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    quadratic_test!(quadratic_tests,
+        // The example races, holding the button for x of t ms: x·(t - x) > d
+        race_7_9: -1, 7, -9, 0..8 => 4,
+        race_15_40: -1, 15, -40, 0..16 => 8,
+        exact_roots: -1, 30, -200, 0..31 => 9,
+        big_race: -1, 71530, -940_200, 0..71531 => 71503,
+        touching: -1, 4, -4, 0..5 => 0,
+        never: -1, 0, -1, -10..10 => 0,
+        upward_outside_roots: 1, 0, -4, -5..6 => 6,
+        upward_double_root: 1, -4, 4, 0..5 => 4,
+        upward_always: 1, 0, 1, -3..3 => 6,
+        linear_increasing: 0, 2, -5, 0..10 => 7,
+        linear_decreasing: 0, -3, 7, -2..5 => 5,
+        constant: 0, 0, 1, 0..3 => 3,
+        empty_range: -1, 10, 0, 5..5 => 0,
+        clamped_range: -1, 10, 0, 3..100 => 7,
+        extreme_range: 0, 1, 0, i64::MIN..i64::MAX => u64::try_from(i64::MAX - 1).unwrap(),
+    );
+
+    #[test]
+    fn isqrt_boundaries() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(
+            isqrt(u128::from(u64::MAX) * u128::from(u64::MAX)),
+            u128::from(u64::MAX)
+        );
+        assert_eq!(isqrt(u128::MAX), u128::from(u64::MAX));
+        assert_eq!(isqrt((1 << 100) - 1), (1 << 50) - 1);
+    }
+
+    proptest! {
+        #[test]
+        fn crt_matches_brute_force(congruences in prop::collection::vec((-20_i64..20, 1_i64..12), 0..4)) {
+            let m = congruences.iter().fold(1, |m, &(_, modulus)| lcm(m, modulus.unsigned_abs()));
+            let brute = (0..i64::try_from(m).unwrap())
+                .find(|x| congruences.iter().all(|&(r, n)| (x - r).rem_euclid(n) == 0));
+
+            prop_assert_eq!(crt(congruences.clone()), brute.map(|x| (x, i64::try_from(m).unwrap())));
+        }
+
+        #[test]
+        fn quadratic_matches_brute_force(a in -5_i64..5, b in -20_i64..20, c in -50_i64..50, start in -20_i64..20, len in 0_i64..30) {
+            let range = start..start + len;
+            let brute = range.clone().filter(|&x| a * x * x + b * x + c > 0).count();
+
+            prop_assert_eq!(count_quadratic_positive(a, b, c, range), u64::try_from(brute).unwrap());
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hash},
+};
+
+/// How often a [`Memo`] found a value it had already computed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// A cache of computed values, for dynamic programming over recursive
+/// functions.
+///
+/// The hasher can be swapped out with [`with_hasher`](Memo::with_hasher),
+/// since the cache lookups are often the hot path of a solution.
+#[derive(Clone, Debug)]
+pub struct Memo<K, V, S = RandomState> {
+    cache: HashMap<K, V, S>,
+    stats: MemoStats,
+}
+
+impl<K, V> Memo<K, V> {
+    #[must_use]
+    pub fn new() -> Memo<K, V> {
+        Memo::with_hasher(RandomState::new())
+    }
+
+    /// A cache with room for `capacity` values before it reallocates
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Memo<K, V> {
+        Memo::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo::new()
+    }
+}
+
+impl<K, V, S> Memo<K, V, S> {
+    #[must_use]
+    pub fn with_hasher(hasher: S) -> Memo<K, V, S> {
+        Memo::with_capacity_and_hasher(0, hasher)
+    }
+
+    #[must_use]
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Memo<K, V, S> {
+        Memo {
+            cache: HashMap::with_capacity_and_hasher(capacity, hasher),
+            stats: MemoStats::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> MemoStats {
+        self.stats
+    }
+
+    /// The number of cached values
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forgets every cached value, keeping the stats
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl<K, V, S> Memo<K, V, S>
+where
+    K: Eq + Hash,
+    V: Clone,
+    S: BuildHasher,
+{
+    /// The cached value for `key`, without counting towards the stats
+    #[must_use]
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// The cached value for `key`, or the value `f` computes for it. `f` gets
+    /// the memo back so it can look up other values along the way.
+    pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> V
+    where
+        F: FnOnce(&mut Self, &K) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+
+        let value = f(self, &key);
+        self.cache.insert(key, value.clone());
+
+        value
+    }
+
+    /// Calls the recursive function `f` with `key`, caching the result of it
+    /// and of every call it makes to the `recurse` callback it's given.
+    ///
+    /// ```
+    /// # use aoc_helpers::memo::Memo;
+    /// let mut memo = Memo::new();
+    /// let fibonacci = |&n: &u64, recurse: &mut dyn FnMut(u64) -> u64| {
+    ///     if n < 2 { n } else { recurse(n - 1) + recurse(n - 2) }
+    /// };
+    ///
+    /// assert_eq!(memo.compute(90, &fibonacci), 2_880_067_194_370_816_120);
+    /// assert_eq!(memo.stats().misses, 91);
+    /// ```
+    pub fn compute<F>(&mut self, key: K, f: &F) -> V
+    where
+        F: Fn(&K, &mut dyn FnMut(K) -> V) -> V,
+    {
+        self.get_or_insert_with(key, |memo, key| f(key, &mut |next| memo.compute(next, f)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Takes the key by reference to match what `compute` expects
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn grid_paths(&(x, y): &(u32, u32), recurse: &mut dyn FnMut((u32, u32)) -> u64) -> u64 {
+        if x == 0 || y == 0 {
+            1
+        } else {
+            recurse((x - 1, y)) + recurse((x, y - 1))
+        }
+    }
+
+    #[test]
+    fn compute_counts_hits_and_misses() {
+        let mut memo = Memo::with_capacity(100);
+
+        assert_eq!(memo.compute((3, 3), &grid_paths), 20);
+
+        // Every point with x, y <= 3 except (0, 0), which is never reached
+        assert_eq!(memo.len(), 15);
+        assert_eq!(
+            memo.stats(),
+            MemoStats {
+                hits: 4,
+                misses: 15
+            }
+        );
+
+        assert_eq!(memo.compute((2, 3), &grid_paths), 10);
+        assert_eq!(memo.stats().hits, 5);
+        assert_eq!(memo.get(&(1, 1)), Some(&2));
+    }
+
+    #[test]
+    fn get_or_insert_with() {
+        let mut memo = Memo::new();
+
+        let first = memo.get_or_insert_with("a", |memo, _| memo.len());
+        let second = memo.get_or_insert_with("b", |memo, _| memo.len());
+        let again = memo.get_or_insert_with("a", |_, _| unreachable!());
+
+        assert_eq!((first, second, again), (0, 1, 0));
+
+        memo.clear();
+
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats(), MemoStats { hits: 1, misses: 2 });
+    }
+}
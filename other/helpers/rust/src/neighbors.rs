@@ -357,19 +357,27 @@ where
 }
 
 pub trait Grid2DMut: Grid2D {
-    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Self::Item>;
-    fn iter_mut(&mut self) -> impl Iterator<Item = (usize, usize, &mut Self::Item)>;
+    fn set(&mut self, x: usize, y: usize, item: Self::Item) -> Option<Self::Item>;
+}
 
+impl<T> Grid2DMut for &mut T
+where
+    T: Grid2DMut,
+{
     fn set(&mut self, x: usize, y: usize, item: Self::Item) -> Option<Self::Item> {
-        let space = self.get_mut(x, y)?;
-
-        Some(std::mem::replace(space, item))
+        (**self).set(x, y, item)
     }
 }
 
-impl<T> Grid2DMut for &mut T
+/// Grids that store their items directly and can lend them out mutably
+pub trait Grid2DRefMut: Grid2DMut {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Self::Item>;
+    fn iter_mut(&mut self) -> impl Iterator<Item = (usize, usize, &mut Self::Item)>;
+}
+
+impl<T> Grid2DRefMut for &mut T
 where
-    T: Grid2DMut,
+    T: Grid2DRefMut,
 {
     fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Self::Item> {
         (**self).get_mut(x, y)
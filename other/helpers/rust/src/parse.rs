@@ -0,0 +1,703 @@
+use std::{fmt, iter::Peekable, mem, str::FromStr};
+
+use crate::{Error, ParseErrorKind};
+
+/// A slice of puzzle input that remembers where it came from, so parse errors
+/// can point at the offending line and column.
+///
+/// Lines and columns are 1-based, and columns count characters rather than
+/// bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    pub text: &'a str,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl<'a> Span<'a> {
+    #[must_use]
+    pub fn new(text: &'a str) -> Span<'a> {
+        Span {
+            text,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// The span of `self.text[start..end]`
+    #[must_use]
+    pub fn slice(self, start: usize, end: usize) -> Span<'a> {
+        let skipped = &self.text[..start];
+
+        let (line, column) = match skipped.rfind('\n') {
+            Some(i) => (
+                self.line + skipped.matches('\n').count(),
+                skipped[i + 1..].chars().count() + 1,
+            ),
+            None => (self.line, self.column + skipped.chars().count()),
+        };
+
+        Span {
+            text: &self.text[start..end],
+            line,
+            column,
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.text.is_empty()
+    }
+
+    #[must_use]
+    pub fn error(self, kind: ParseErrorKind) -> Error {
+        Error::Parse {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+
+    #[must_use]
+    pub fn trim(self) -> Span<'a> {
+        let start = self.text.len() - self.text.trim_start().len();
+        let end = self.text.trim_end().len().max(start);
+
+        self.slice(start, end)
+    }
+
+    /// Splits into lines, dropping `\n` and `\r\n` line endings
+    pub fn lines(self) -> impl Iterator<Item = Span<'a>> {
+        self.text
+            .split_inclusive('\n')
+            .scan(0, move |offset, line| {
+                let start = *offset;
+                *offset += line.len();
+
+                let line = line.strip_suffix('\n').unwrap_or(line);
+                let line = line.strip_suffix('\r').unwrap_or(line);
+
+                Some(self.slice(start, start + line.len()))
+            })
+    }
+
+    /// Splits into sections separated by one or more blank lines
+    pub fn sections(self) -> impl Iterator<Item = Span<'a>> {
+        let mut sections = Vec::new();
+        let mut current: Option<(usize, usize)> = None;
+
+        for line in self.lines() {
+            let start = line.text.as_ptr() as usize - self.text.as_ptr() as usize;
+            let end = start + line.text.len();
+
+            if line.text.trim().is_empty() {
+                if let Some((start, end)) = current.take() {
+                    sections.push(self.slice(start, end));
+                }
+            } else if let Some((_, current_end)) = &mut current {
+                *current_end = end;
+            } else {
+                current = Some((start, end));
+            }
+        }
+
+        if let Some((start, end)) = current {
+            sections.push(self.slice(start, end));
+        }
+
+        sections.into_iter()
+    }
+
+    /// Splits on runs of whitespace, skipping empty tokens
+    pub fn words(self) -> impl Iterator<Item = Span<'a>> {
+        self.text
+            .split(char::is_whitespace)
+            .filter(|word| !word.is_empty())
+            .map(move |word| {
+                let start = word.as_ptr() as usize - self.text.as_ptr() as usize;
+
+                self.slice(start, start + word.len())
+            })
+    }
+
+    /// Splits on any of `separators`, keeping empty pieces
+    pub fn split(self, separators: &'a [char]) -> impl Iterator<Item = Span<'a>> {
+        self.text.split(separators).map(move |piece| {
+            let start = piece.as_ptr() as usize - self.text.as_ptr() as usize;
+
+            self.slice(start, start + piece.len())
+        })
+    }
+
+    /// # Errors
+    /// * `Error::Parse` - `T::from_str` failed on the (untrimmed) text
+    pub fn parse<T>(self) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.text.parse().map_err(|e: T::Err| {
+            self.error(ParseErrorKind::InvalidValue {
+                text: self.text.to_owned(),
+                reason: e.to_string(),
+            })
+        })
+    }
+
+    /// # Errors
+    /// * `Error::Parse` - the text doesn't start with `prefix`
+    pub fn strip_prefix(self, prefix: &str) -> Result<Span<'a>, Error> {
+        if self.text.starts_with(prefix) {
+            Ok(self.slice(prefix.len(), self.text.len()))
+        } else {
+            Err(self.error(ParseErrorKind::Expected(prefix.to_owned())))
+        }
+    }
+
+    /// # Errors
+    /// * `Error::Parse` - the text doesn't contain `separator`
+    pub fn split_once(self, separator: &str) -> Result<(Span<'a>, Span<'a>), Error> {
+        match self.text.find(separator) {
+            Some(i) => Ok((
+                self.slice(0, i),
+                self.slice(i + separator.len(), self.text.len()),
+            )),
+            None => Err(self
+                .slice(self.text.len(), self.text.len())
+                .error(ParseErrorKind::Expected(separator.to_owned()))),
+        }
+    }
+
+    /// Extracts every integer in the text, e.g. `[12, -3, 4]` from
+    /// `"x=12, y=-3 (4)"`. A `-` only counts as a sign when it isn't preceded
+    /// by a letter or digit, so `"1-3"` is `[1, 3]`.
+    ///
+    /// # Errors
+    /// * `Error::Parse` - an integer didn't fit into `T`
+    pub fn ints<T>(self) -> Result<Vec<T>, Error>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let bytes = self.text.as_bytes();
+        let mut ints = Vec::new();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+
+            let mut start = i;
+
+            if start > 0
+                && bytes[start - 1] == b'-'
+                && (start == 1 || !bytes[start - 2].is_ascii_alphanumeric())
+            {
+                start -= 1;
+            }
+
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+
+            ints.push(self.slice(start, i).parse()?);
+        }
+
+        Ok(ints)
+    }
+
+    /// Returns the trimmed text after `header`, e.g. `"7  15   30"` for
+    /// `header_value("Time:")` on `"Time:      7  15   30"`
+    ///
+    /// # Errors
+    /// * `Error::Parse` - the text doesn't start with `header`
+    pub fn header_value(self, header: &str) -> Result<Span<'a>, Error> {
+        Ok(self.trim().strip_prefix(header)?.trim())
+    }
+
+    /// Splits a `key: value` pair, trimming both sides
+    ///
+    /// # Errors
+    /// * `Error::Parse` - there's no `:` in the text
+    pub fn key_value(self) -> Result<(Span<'a>, Span<'a>), Error> {
+        let (key, value) = self.split_once(":")?;
+
+        Ok((key.trim(), value.trim()))
+    }
+
+    /// Splits an `a = (b, c)` node, trimming each part
+    ///
+    /// # Errors
+    /// * `Error::Parse` - the text doesn't match the `a = (b, c)` pattern
+    pub fn node_pair(self) -> Result<(Span<'a>, Span<'a>, Span<'a>), Error> {
+        let (node, pair) = self.split_once("=")?;
+        let pair = pair.trim().strip_prefix("(")?;
+        let (left, right) = pair.split_once(",")?;
+        let (right, rest) = right.split_once(")")?;
+
+        if !rest.trim().is_empty() {
+            return Err(rest
+                .trim()
+                .error(ParseErrorKind::Expected("end of line".to_owned())));
+        }
+
+        Ok((node.trim(), left.trim(), right.trim()))
+    }
+}
+
+impl<'a> From<&'a str> for Span<'a> {
+    fn from(text: &'a str) -> Self {
+        Span::new(text)
+    }
+}
+
+impl fmt::Display for Span<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Span<'_>> {
+    Span::new(input).lines()
+}
+
+pub fn sections(input: &str) -> impl Iterator<Item = Span<'_>> {
+    Span::new(input).sections()
+}
+
+/// # Errors
+/// * `Error::Parse` - an integer didn't fit into `T`
+pub fn ints<T>(s: &str) -> Result<Vec<T>, Error>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    Span::new(s).ints()
+}
+
+/// # Errors
+/// * `Error::Parse` - `line` doesn't start with `header`
+pub fn header_value<'a>(header: &str, line: &'a str) -> Result<&'a str, Error> {
+    Span::new(line).header_value(header).map(|value| value.text)
+}
+
+/// A value that can be captured by [`scan`]
+pub trait Capture<'a>: Sized {
+    /// # Errors
+    /// * `Error::Parse` - the captured text isn't a valid `Self`
+    fn capture(span: Span<'a>) -> Result<Self, Error>;
+}
+
+impl<'a> Capture<'a> for Span<'a> {
+    fn capture(span: Span<'a>) -> Result<Self, Error> {
+        Ok(span)
+    }
+}
+
+impl<'a> Capture<'a> for &'a str {
+    fn capture(span: Span<'a>) -> Result<Self, Error> {
+        Ok(span.text)
+    }
+}
+
+/// Captures a whitespace separated list
+impl<'a, T> Capture<'a> for Vec<T>
+where
+    T: Capture<'a>,
+{
+    fn capture(span: Span<'a>) -> Result<Self, Error> {
+        span.words().map(T::capture).collect()
+    }
+}
+
+macro_rules! capture_from_str {
+    ($($type:ty),*) => {
+        $(
+            impl Capture<'_> for $type {
+                fn capture(span: Span<'_>) -> Result<Self, Error> {
+                    span.parse()
+                }
+            }
+        )*
+    };
+}
+
+capture_from_str!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char, String
+);
+
+/// A tuple of values captured by [`scan`], one per capture in the format
+pub trait Captures<'a>: Sized {
+    const LEN: usize;
+
+    /// # Errors
+    /// * `Error::Parse` - a captured text isn't valid for its type
+    fn from_captures(captures: &[Span<'a>]) -> Result<Self, Error>;
+}
+
+macro_rules! captures_tuple {
+    ($len:literal; $($type:ident $index:tt),+) => {
+        impl<'a, $($type),+> Captures<'a> for ($($type,)+)
+        where
+            $($type: Capture<'a>),+
+        {
+            const LEN: usize = $len;
+
+            fn from_captures(captures: &[Span<'a>]) -> Result<Self, Error> {
+                Ok(($($type::capture(captures[$index])?,)+))
+            }
+        }
+    };
+}
+
+captures_tuple!(1; A 0);
+captures_tuple!(2; A 0, B 1);
+captures_tuple!(3; A 0, B 1, C 2);
+captures_tuple!(4; A 0, B 1, C 2, D 3);
+captures_tuple!(5; A 0, B 1, C 2, D 3, E 4);
+captures_tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+
+enum Piece {
+    Literal(String),
+    Capture,
+}
+
+/// Counts the captures in a [`scan`] format. [`scan!`](crate::scan) calls
+/// this at compile time.
+///
+/// # Panics
+/// If the format is malformed
+#[must_use]
+pub const fn format_captures(format: &str) -> usize {
+    let bytes = format.as_bytes();
+    let mut captures = 0;
+    let mut after_capture = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if (bytes[i] == b'{' || bytes[i] == b'}') && i + 1 < bytes.len() && bytes[i + 1] == bytes[i]
+        {
+            after_capture = false;
+            i += 2;
+        } else if bytes[i] == b'{' {
+            assert!(
+                !after_capture,
+                "captures in a scan format must be separated by literal text"
+            );
+
+            i += 1;
+
+            while i < bytes.len() && bytes[i] != b'}' {
+                assert!(
+                    bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_',
+                    "scan capture names must be identifiers"
+                );
+                i += 1;
+            }
+
+            assert!(i < bytes.len(), "unclosed `{{` in scan format");
+
+            captures += 1;
+            after_capture = true;
+            i += 1;
+        } else {
+            assert!(bytes[i] != b'}', "unmatched `}}` in scan format");
+
+            after_capture = false;
+            i += 1;
+        }
+    }
+
+    captures
+}
+
+fn pieces(format: &str) -> Peekable<impl Iterator<Item = Piece>> {
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' if chars.peek() == Some(&c) => {
+                chars.next();
+                literal.push(c);
+            }
+            '{' => {
+                chars.by_ref().find(|&c| c == '}');
+
+                if !literal.is_empty() {
+                    pieces.push(Piece::Literal(mem::take(&mut literal)));
+                }
+
+                pieces.push(Piece::Capture);
+            }
+            c => literal.push(c),
+        }
+    }
+
+    if !literal.is_empty() {
+        pieces.push(Piece::Literal(literal));
+    }
+
+    pieces.into_iter().peekable()
+}
+
+/// Matches `input` against a format like `"Game {id}: {rest}"`, returning a
+/// tuple with one value per `{name}` capture. Names are only there for
+/// readability, and `{{` and `}}` match literal braces.
+///
+/// Each capture runs up to the first occurrence of the literal text after it
+/// (or the end of the input), and is trimmed before being converted.
+///
+/// # Errors
+/// * `Error::Parse` - the input didn't contain a literal from the format where
+///   expected, had trailing text, or a capture couldn't be converted
+///
+/// # Panics
+/// If the format is malformed, or has a different number of captures than `C`
+pub fn scan<'a, C>(input: impl Into<Span<'a>>, format: &str) -> Result<C, Error>
+where
+    C: Captures<'a>,
+{
+    assert_eq!(
+        format_captures(format),
+        C::LEN,
+        "scan format {format:?} doesn't match the number of captured values"
+    );
+
+    let mut rest = input.into();
+    let mut captures = Vec::with_capacity(C::LEN);
+    let mut pieces = pieces(format);
+
+    while let Some(piece) = pieces.next() {
+        match piece {
+            Piece::Literal(literal) => rest = rest.strip_prefix(&literal)?,
+            Piece::Capture => {
+                let end = match pieces.peek() {
+                    Some(Piece::Literal(literal)) => rest.split_once(literal)?.0.text.len(),
+                    _ => rest.text.len(),
+                };
+
+                captures.push(rest.slice(0, end).trim());
+                rest = rest.slice(end, rest.text.len());
+            }
+        }
+    }
+
+    if !rest.is_empty() {
+        return Err(rest.error(ParseErrorKind::Expected("end of line".to_owned())));
+    }
+
+    C::from_captures(&captures)
+}
+
+/// Matches a line against a format, see [`scan`](crate::parse::scan). The
+/// format is checked at compile time, and the captured types can either be
+/// inferred or listed after it.
+///
+/// ```
+/// use aoc_helpers::{parse::Span, scan};
+///
+/// let (id, rest): (u32, Span) = scan!("Game 12: 3 blue", "Game {id}: {rest}")?;
+/// assert_eq!((id, rest.text, rest.column), (12, "3 blue", 10));
+///
+/// let (node, left, right) = scan!("AAA = (BBB, CCC)", "{node} = ({left}, {right})", &str, &str, &str)?;
+/// assert_eq!((node, left, right), ("AAA", "BBB", "CCC"));
+/// # Ok::<(), aoc_helpers::Error>(())
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($input:expr, $format:literal) => {{
+        const _: usize = $crate::parse::format_captures($format);
+
+        $crate::parse::scan($input, $format)
+    }};
+    ($input:expr, $format:literal, $($type:ty),+ $(,)?) => {{
+        const _: usize = $crate::parse::format_captures($format);
+
+        $crate::parse::scan::<($($type,)+)>($input, $format)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(line: usize, column: usize, kind: ParseErrorKind) -> Error {
+        Error::Parse { line, column, kind }
+    }
+
+    macro_rules! ints_test {
+        ($suite:ident, $($name:ident: $type:ty, $input:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(ints::<$type>($input), $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    ints_test!(ints_tests,
+        spaced: u32, "83 86  6 31" => Ok(vec![83, 86, 6, 31]),
+        signed: i64, "10 -3 0 -22" => Ok(vec![10, -3, 0, -22]),
+        mixed_text: i32, "x=12, y=-3 (4)" => Ok(vec![12, -3, 4]),
+        dash_between_digits: i32, "1-3 a" => Ok(vec![1, 3]),
+        dash_after_word: i32, "seed-1" => Ok(vec![1]),
+        none: u8, "no numbers" => Ok(vec![]),
+        unsigned_negative: u32, "4 -5" => Err(parse_error(1, 3, ParseErrorKind::InvalidValue {
+            text: "-5".to_owned(),
+            reason: "invalid digit found in string".to_owned(),
+        })),
+        overflow: u8, "255 256" => Err(parse_error(1, 5, ParseErrorKind::InvalidValue {
+            text: "256".to_owned(),
+            reason: "number too large to fit in target type".to_owned(),
+        })),
+    );
+
+    #[test]
+    fn header_value_trims() {
+        assert_eq!(header_value("Time:", "Time:      7  15   30"), Ok("7  15   30"));
+        assert_eq!(
+            header_value("Time:", "Distance:  9"),
+            Err(parse_error(1, 1, ParseErrorKind::Expected("Time:".to_owned())))
+        );
+    }
+
+    #[test]
+    fn line_positions() {
+        let lines = lines("ab\r\n\ncd\n  ef").collect::<Vec<_>>();
+
+        assert_eq!(
+            lines.iter().map(|l| (l.text, l.line, l.column)).collect::<Vec<_>>(),
+            vec![("ab", 1, 1), ("", 2, 1), ("cd", 3, 1), ("  ef", 4, 1)]
+        );
+        assert_eq!(lines[3].trim().column, 3);
+    }
+
+    #[test]
+    fn sections_keep_line_numbers() {
+        let input = "\nseeds: 79 14\n\n\nseed-to-soil map:\n50 98 2\n52 50 48\n";
+        let sections = sections(input).collect::<Vec<_>>();
+
+        assert_eq!(
+            sections.iter().map(|s| (s.text, s.line)).collect::<Vec<_>>(),
+            vec![
+                ("seeds: 79 14", 2),
+                ("seed-to-soil map:\n50 98 2\n52 50 48", 5)
+            ]
+        );
+
+        let word = sections[1].lines().nth(1).unwrap().words().nth(1).unwrap();
+        assert_eq!((word.text, word.line, word.column), ("98", 6, 4));
+    }
+
+    #[test]
+    fn split_positions() {
+        let pieces = Span::new("3 blue, 4 red; 1 red")
+            .split(&[';', ','])
+            .map(|piece| (piece.trim().text, piece.trim().column))
+            .collect::<Vec<_>>();
+
+        assert_eq!(pieces, vec![("3 blue", 1), ("4 red", 9), ("1 red", 16)]);
+    }
+
+    #[test]
+    fn key_value() {
+        let (key, value) = Span::new("Card  1: 41 48 | 83").key_value().unwrap();
+
+        assert_eq!((key.text, key.column), ("Card  1", 1));
+        assert_eq!((value.text, value.column), ("41 48 | 83", 10));
+        assert_eq!(
+            Span::new("Card 1 41").key_value(),
+            Err(parse_error(1, 10, ParseErrorKind::Expected(":".to_owned())))
+        );
+    }
+
+    macro_rules! node_pair_test {
+        ($suite:ident, $($name:ident: $input:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        let output = Span::new($input)
+                            .node_pair()
+                            .map(|(a, b, c)| (a.text, b.text, c.text));
+
+                        assert_eq!(output, $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    node_pair_test!(node_pair_tests,
+        node: "AAA = (BBB, CCC)" => Ok(("AAA", "BBB", "CCC")),
+        missing_paren: "AAA = BBB, CCC)" => Err(parse_error(1, 7, ParseErrorKind::Expected("(".to_owned()))),
+        missing_comma: "AAA = (BBB CCC)" => Err(parse_error(1, 16, ParseErrorKind::Expected(",".to_owned()))),
+        trailing_text: "AAA = (BBB, CCC) x" => Err(parse_error(1, 18, ParseErrorKind::Expected("end of line".to_owned()))),
+    );
+
+    macro_rules! scan_test {
+        ($suite:ident, $($name:ident: $input:expr, $format:literal => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        let output = scan::<(u32, &str)>($input, $format);
+
+                        assert_eq!(output, $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    scan_test!(scan_tests,
+        game: "Game 12: 3 blue", "Game {id}: {rest}" => Ok((12, "3 blue")),
+        padded_capture: "Card   4:  1 2", "Card {id}: {rest}" => Ok((4, "1 2")),
+        escaped_braces: "{7} x", "{{{n}}} {rest}" => Ok((7, "x")),
+        missing_prefix: "Gme 1: a", "Game {id}: {rest}" => Err(parse_error(1, 1, ParseErrorKind::Expected("Game ".to_owned()))),
+        missing_separator: "Game 1 a", "Game {id}: {rest}" => Err(parse_error(1, 9, ParseErrorKind::Expected(": ".to_owned()))),
+        trailing_text: "(1, a) b", "({n}, {rest})" => Err(parse_error(1, 7, ParseErrorKind::Expected("end of line".to_owned()))),
+        invalid_capture: "Game one: a", "Game {id}: {rest}" => Err(parse_error(1, 6, ParseErrorKind::InvalidValue {
+            text: "one".to_owned(),
+            reason: "invalid digit found in string".to_owned(),
+        })),
+    );
+
+    #[test]
+    fn scan_macro() {
+        let line = lines("x\nCard 1: 41 48 | 83 86").nth(1).unwrap();
+        let (id, winning, numbers) = scan!(
+            line,
+            "Card {id}: {winning} | {numbers}",
+            u8,
+            Vec<u32>,
+            Vec<u32>
+        )
+        .unwrap();
+
+        assert_eq!((id, winning, numbers), (1, vec![41, 48], vec![83, 86]));
+        assert_eq!(
+            scan!(line, "Card {id}: {winning}!", u8, Span),
+            Err(parse_error(2, 22, ParseErrorKind::Expected("!".to_owned())))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't match the number of captured values")]
+    fn scan_capture_count_mismatch() {
+        let _ = scan::<(u32,)>("1 2", "{a} {b}");
+    }
+}
@@ -0,0 +1,295 @@
+use std::ops::Range;
+
+use crate::ranges::{Int, RangeMap, RangeSet};
+
+/// A function on integers made of offset pieces over disjoint ranges, mapping
+/// every value outside the pieces to itself.
+///
+/// This is the shape of the almanac maps in 2023 day 5, where chaining maps is
+/// [`compose`](PiecewiseMap::compose) and finding the lowest location for a
+/// range of seeds is [`min_output_over`](PiecewiseMap::min_output_over).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PiecewiseMap<T> {
+    // Identity pieces are never stored, so equal functions compare equal
+    offsets: RangeMap<T, T>,
+}
+
+impl<T> Default for PiecewiseMap<T> {
+    fn default() -> Self {
+        PiecewiseMap {
+            offsets: RangeMap::default(),
+        }
+    }
+}
+
+impl<T: Int> PiecewiseMap<T> {
+    /// The identity function
+    #[must_use]
+    pub fn new() -> PiecewiseMap<T> {
+        PiecewiseMap::default()
+    }
+
+    /// Maps `source` onto the range starting at `dest_start`, replacing any
+    /// pieces it overlaps
+    pub fn insert(&mut self, source: Range<T>, dest_start: T) {
+        let offset = dest_start.wrapping_sub(source.start);
+
+        if offset == T::ZERO {
+            self.offsets.remove(source);
+        } else {
+            self.offsets.insert(source, offset);
+        }
+    }
+
+    /// The pieces that don't map values to themselves, as
+    /// `(source, dest_start)` pairs
+    pub fn pieces(&self) -> impl Iterator<Item = (Range<T>, T)> + '_ {
+        self.offsets
+            .iter()
+            .map(|(range, &offset)| (range.clone(), range.start.wrapping_add(offset)))
+    }
+
+    #[must_use]
+    pub fn apply(&self, value: T) -> T {
+        value.wrapping_add(self.offsets.get(value).copied().unwrap_or(T::ZERO))
+    }
+
+    /// Every output for an input in `range`
+    #[must_use]
+    pub fn apply_to_range(&self, range: Range<T>) -> RangeSet<T> {
+        self.segments(range)
+            .map(|(range, offset)| shift(&range, offset))
+            .collect()
+    }
+
+    /// Every output for an input in `set`
+    #[must_use]
+    pub fn apply_to_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        set.iter()
+            .flat_map(|range| self.segments(range.clone()))
+            .map(|(range, offset)| shift(&range, offset))
+            .collect()
+    }
+
+    /// The smallest output for an input in `range`, or `None` if it's empty
+    #[must_use]
+    pub fn min_output_over(&self, range: Range<T>) -> Option<T> {
+        self.segments(range)
+            .map(|(range, offset)| range.start.wrapping_add(offset))
+            .min()
+    }
+
+    /// The function that applies `self` and then `then`
+    #[must_use]
+    pub fn compose(&self, then: &PiecewiseMap<T>) -> PiecewiseMap<T> {
+        let mut out = PiecewiseMap::new();
+
+        for (range, &offset) in self.offsets.iter() {
+            for (image, then_offset) in then.segments(shift(range, offset)) {
+                out.insert_offset(
+                    shift(&image, T::ZERO.wrapping_sub(offset)),
+                    offset.wrapping_add(then_offset),
+                );
+            }
+        }
+
+        // Where `self` is the identity, the composition is just `then`
+        let domain = self.offsets.domain();
+
+        for (range, &then_offset) in then.offsets.iter() {
+            for uncovered in RangeSet::from(range.clone()).difference(&domain).iter() {
+                out.insert_offset(uncovered.clone(), then_offset);
+            }
+        }
+
+        out
+    }
+
+    /// The inverse function, or `None` if the function isn't a bijection
+    /// (some pieces map onto the same values, or onto values that are left as
+    /// they are)
+    #[must_use]
+    pub fn inverse(&self) -> Option<PiecewiseMap<T>> {
+        let domain = self.offsets.domain();
+        let mut image = RangeSet::new();
+        let mut inverse = PiecewiseMap::new();
+
+        for (range, &offset) in self.offsets.iter() {
+            let shifted = shift(range, offset);
+
+            if !image
+                .intersection(&RangeSet::from(shifted.clone()))
+                .is_empty()
+            {
+                return None;
+            }
+
+            image.insert(shifted.clone());
+            inverse.insert_offset(shifted, T::ZERO.wrapping_sub(offset));
+        }
+
+        (image == domain).then_some(inverse)
+    }
+
+    fn insert_offset(&mut self, range: Range<T>, offset: T) {
+        if offset != T::ZERO {
+            self.offsets.insert(range, offset);
+        }
+    }
+
+    /// Splits `range` into the sub-ranges covered by each piece, along with
+    /// their offsets, including the identity gaps between pieces
+    fn segments(&self, range: Range<T>) -> impl Iterator<Item = (Range<T>, T)> + '_ {
+        let mut pieces = self
+            .offsets
+            .iter()
+            .skip_while(move |(piece, _)| piece.end <= range.start)
+            .peekable();
+        let mut start = range.start;
+        let end = range.end;
+
+        std::iter::from_fn(move || {
+            if start >= end {
+                return None;
+            }
+
+            let segment = match pieces.peek() {
+                Some((piece, &offset)) if piece.start <= start => {
+                    let segment = (start..piece.end.min(end), offset);
+                    pieces.next();
+                    segment
+                }
+                Some((piece, _)) if piece.start < end => (start..piece.start, T::ZERO),
+                _ => (start..end, T::ZERO),
+            };
+
+            start = segment.0.end;
+
+            Some(segment)
+        })
+    }
+}
+
+impl<T: Int> FromIterator<(Range<T>, T)> for PiecewiseMap<T> {
+    /// Builds a map from `(source, dest_start)` pieces, with later pieces
+    /// replacing earlier ones where they overlap
+    fn from_iter<I: IntoIterator<Item = (Range<T>, T)>>(iter: I) -> Self {
+        let mut map = PiecewiseMap::new();
+
+        for (source, dest_start) in iter {
+            map.insert(source, dest_start);
+        }
+
+        map
+    }
+}
+
+fn shift<T: Int>(range: &Range<T>, offset: T) -> Range<T> {
+    range.start.wrapping_add(offset)..range.end.wrapping_add(offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn seed_to_soil() -> PiecewiseMap<u64> {
+        PiecewiseMap::from_iter([(98..100, 50), (50..98, 52)])
+    }
+
+    fn soil_to_fertilizer() -> PiecewiseMap<u64> {
+        PiecewiseMap::from_iter([(15..52, 0), (52..54, 37), (0..15, 39)])
+    }
+
+    macro_rules! apply_test {
+        ($suite:ident, $($name:ident: $input:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        let map = seed_to_soil().compose(&soil_to_fertilizer());
+
+                        assert_eq!(map.apply($input), $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    // Seeds from the example almanac, with the fertilizer numbers it lists
+    apply_test!(compose_tests,
+        seed_79: 79 => 81,
+        seed_14: 14 => 53,
+        seed_55: 55 => 57,
+        seed_13: 13 => 52,
+        unmapped_by_both: 200 => 200,
+    );
+
+    #[test]
+    fn apply_to_range() {
+        let map = seed_to_soil();
+
+        assert_eq!(map.apply_to_range(45..60).ranges(), &[45..50, 52..62]);
+        assert_eq!(map.apply_to_range(97..100).ranges(), &[50..52, 99..100]);
+        assert_eq!(map.min_output_over(96..100), Some(50));
+        assert_eq!(map.min_output_over(10..10), None);
+    }
+
+    #[test]
+    fn inverse() {
+        let map = seed_to_soil();
+        let inverse = map.inverse().unwrap();
+
+        assert_eq!(inverse.apply(50), 98);
+        assert_eq!(inverse.apply(99), 97);
+        assert_eq!(map.compose(&inverse), PiecewiseMap::new());
+
+        // 10 and 20 would both map to 20
+        assert_eq!(PiecewiseMap::from_iter([(10_u8..11, 20)]).inverse(), None);
+    }
+
+    fn piecewise_map() -> impl Strategy<Value = PiecewiseMap<u8>> {
+        prop::collection::vec((0_u8..100, 0_u8..20, 0_u8..100), 0..6).prop_map(|pieces| {
+            pieces
+                .into_iter()
+                .map(|(start, len, dest)| (start..start + len, dest))
+                .collect()
+        })
+    }
+
+    // Keeps outputs inside the u8 range by only checking inputs a map can't
+    // push past the end
+    const INPUTS: Range<u8> = 0..120;
+
+    proptest! {
+        #[test]
+        fn compose_applies_in_order(a in piecewise_map(), b in piecewise_map()) {
+            let composed = a.compose(&b);
+
+            for x in INPUTS {
+                prop_assert_eq!(composed.apply(x), b.apply(a.apply(x)));
+            }
+        }
+
+        #[test]
+        fn apply_to_range_matches_apply(map in piecewise_map(), start in INPUTS, len in 0_u8..40) {
+            let range = start..(start + len).min(INPUTS.end);
+            let expected = range.clone().map(|x| map.apply(x)).map(|y| y..y + 1).collect::<RangeSet<_>>();
+
+            prop_assert_eq!(map.apply_to_range(range.clone()), expected.clone());
+            prop_assert_eq!(map.min_output_over(range), expected.min());
+        }
+
+        #[test]
+        fn inverse_undoes_map(map in piecewise_map()) {
+            if let Some(inverse) = map.inverse() {
+                for x in INPUTS {
+                    prop_assert_eq!(inverse.apply(map.apply(x)), x);
+                }
+            }
+        }
+    }
+}
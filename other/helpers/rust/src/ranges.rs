@@ -0,0 +1,552 @@
+use std::{
+    fmt,
+    hash::Hash,
+    ops::{Add, Range, Sub},
+};
+
+/// The integer types that [`RangeSet`] and [`RangeMap`] work with
+pub trait Int: Copy + Ord + Hash + fmt::Debug + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    #[must_use]
+    fn wrapping_add(self, rhs: Self) -> Self;
+    #[must_use]
+    fn wrapping_sub(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_int {
+    ($($type:ty),*) => {
+        $(
+            impl Int for $type {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn wrapping_add(self, rhs: Self) -> Self {
+                    <$type>::wrapping_add(self, rhs)
+                }
+
+                fn wrapping_sub(self, rhs: Self) -> Self {
+                    <$type>::wrapping_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A set of integers stored as sorted, disjoint half-open ranges.
+///
+/// Overlapping and touching ranges are always coalesced, so two sets with the
+/// same values compare equal no matter how they were built.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[allow(clippy::module_name_repetitions)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet { ranges: Vec::new() }
+    }
+}
+
+impl<T: Int> RangeSet<T> {
+    #[must_use]
+    pub fn new() -> RangeSet<T> {
+        RangeSet::default()
+    }
+
+    #[must_use]
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Range<T>> {
+        self.ranges.iter()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of values in the set, which can overflow `T` for sets
+    /// covering most of a signed type
+    #[must_use]
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::ZERO, |len, range| len + (range.end - range.start))
+    }
+
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    #[must_use]
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|range| range.end - T::ONE)
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|range| range.end <= value);
+
+        self.ranges.get(i).is_some_and(|range| range.start <= value)
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // Ranges that overlap or touch `range` get merged into it
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let merged = if first < last {
+            range.start.min(self.ranges[first].start)..range.end.max(self.ranges[last - 1].end)
+        } else {
+            range
+        };
+
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+
+        if first == last {
+            return;
+        }
+
+        let before = self.ranges[first].start..range.start;
+        let after = range.end..self.ranges[last - 1].end;
+
+        self.ranges.splice(
+            first..last,
+            [before, after].into_iter().filter(|r| !r.is_empty()),
+        );
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        self.iter().chain(other.iter()).cloned().collect()
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        RangeSet { ranges }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut out = self.clone();
+
+        for range in other.iter() {
+            out.remove(range.clone());
+        }
+
+        out
+    }
+}
+
+impl<T: Int> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut ranges = iter
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .collect::<Vec<_>>();
+        ranges.sort_unstable_by_key(|range| range.start);
+
+        let mut coalesced: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+
+        for range in ranges {
+            match coalesced.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => coalesced.push(range),
+            }
+        }
+
+        RangeSet { ranges: coalesced }
+    }
+}
+
+impl<T: Int> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        RangeSet::from_iter([range])
+    }
+}
+
+/// A map from disjoint half-open ranges of keys to values.
+///
+/// Inserting a range overwrites whatever it overlaps, splitting the pieces it
+/// only partly covers, and touching pieces with equal values are coalesced.
+///
+/// A `RangeMap<T, T>` can also be used as a piecewise-offset function, where
+/// each piece's value is added to keys in its range (wrapping, so unsigned
+/// types can store negative offsets).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[allow(clippy::module_name_repetitions)]
+pub struct RangeMap<K, V> {
+    pieces: Vec<(Range<K>, V)>,
+}
+
+impl<K, V> Default for RangeMap<K, V> {
+    fn default() -> Self {
+        RangeMap { pieces: Vec::new() }
+    }
+}
+
+impl<K, V> RangeMap<K, V>
+where
+    K: Int,
+    V: Clone + PartialEq,
+{
+    #[must_use]
+    pub fn new() -> RangeMap<K, V> {
+        RangeMap::default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Range<K>, &V)> {
+        self.pieces.iter().map(|(range, value)| (range, value))
+    }
+
+    /// The number of pieces in the map
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pieces.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pieces.is_empty()
+    }
+
+    #[must_use]
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    /// The piece containing `key`, along with its value
+    #[must_use]
+    pub fn get_key_value(&self, key: K) -> Option<(&Range<K>, &V)> {
+        let i = self.pieces.partition_point(|(range, _)| range.end <= key);
+
+        self.pieces
+            .get(i)
+            .filter(|(range, _)| range.start <= key)
+            .map(|(range, value)| (range, value))
+    }
+
+    /// The keys covered by the map
+    #[must_use]
+    pub fn domain(&self) -> RangeSet<K> {
+        self.pieces.iter().map(|(range, _)| range.clone()).collect()
+    }
+
+    pub fn insert(&mut self, range: Range<K>, value: V) {
+        if range.is_empty() {
+            return;
+        }
+
+        self.remove(range.clone());
+
+        let mut i = self.pieces.partition_point(|(r, _)| r.end <= range.start);
+        self.pieces.insert(i, (range, value));
+
+        if i + 1 < self.pieces.len() {
+            self.coalesce(i);
+        }
+        if i > 0 {
+            i -= 1;
+            self.coalesce(i);
+        }
+    }
+
+    pub fn remove(&mut self, range: Range<K>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.pieces.partition_point(|(r, _)| r.end <= range.start);
+        let last = self.pieces.partition_point(|(r, _)| r.start < range.end);
+
+        if first == last {
+            return;
+        }
+
+        let (first_range, first_value) = &self.pieces[first];
+        let (last_range, last_value) = &self.pieces[last - 1];
+
+        let before = (first_range.start..range.start, first_value.clone());
+        let after = (range.end..last_range.end, last_value.clone());
+
+        self.pieces.splice(
+            first..last,
+            [before, after].into_iter().filter(|(r, _)| !r.is_empty()),
+        );
+    }
+
+    /// Merges piece `i` with the next piece if they touch and are equal
+    fn coalesce(&mut self, i: usize) {
+        let (a, b) = (&self.pieces[i], &self.pieces[i + 1]);
+
+        if a.0.end == b.0.start && a.1 == b.1 {
+            let end = b.0.end;
+            self.pieces[i].0.end = end;
+            self.pieces.remove(i + 1);
+        }
+    }
+}
+
+impl<T: Int> RangeMap<T, T> {
+    /// Maps `source` onto the range starting at `dest_start`
+    pub fn insert_offset(&mut self, source: Range<T>, dest_start: T) {
+        let offset = dest_start.wrapping_sub(source.start);
+
+        self.insert(source, offset);
+    }
+
+    /// Offsets `key` by the piece containing it
+    #[must_use]
+    pub fn apply(&self, key: T) -> Option<T> {
+        self.get(key).map(|&offset| key.wrapping_add(offset))
+    }
+
+    /// The map that applies `self` and then `then`, defined for the keys where
+    /// both are defined
+    #[must_use]
+    pub fn compose(&self, then: &RangeMap<T, T>) -> RangeMap<T, T> {
+        let mut out = RangeMap::new();
+
+        for (range, offset) in self.iter().map(|(r, &o)| (r, o)) {
+            let image = range.start.wrapping_add(offset)..range.end.wrapping_add(offset);
+            let first = then.pieces.partition_point(|(r, _)| r.end <= image.start);
+
+            for (then_range, &then_offset) in then.pieces[first..].iter().map(|(r, o)| (r, o)) {
+                if then_range.start >= image.end {
+                    break;
+                }
+
+                let overlap = image.start.max(then_range.start)..image.end.min(then_range.end);
+                let source = overlap.start.wrapping_sub(offset)..overlap.end.wrapping_sub(offset);
+
+                out.insert(source, offset.wrapping_add(then_offset));
+            }
+        }
+
+        out
+    }
+}
+
+impl<K, V> FromIterator<(Range<K>, V)> for RangeMap<K, V>
+where
+    K: Int,
+    V: Clone + PartialEq,
+{
+    /// Inserts each piece in order, so later pieces overwrite earlier ones
+    fn from_iter<I: IntoIterator<Item = (Range<K>, V)>>(iter: I) -> Self {
+        let mut map = RangeMap::new();
+
+        for (range, value) in iter {
+            map.insert(range, value);
+        }
+
+        map
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init, clippy::reversed_empty_ranges)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    macro_rules! range_set_test {
+        ($suite:ident, $($name:ident: $ranges:expr, $op:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        let mut set = $ranges.into_iter().collect::<RangeSet<i32>>();
+                        $op(&mut set);
+
+                        assert_eq!(set.ranges(), $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    range_set_test!(range_set_tests,
+        coalesce_overlapping: [5..10, 0..3, 2..6], |_: &mut RangeSet<i32>| () => &[0..10],
+        coalesce_touching: [0..3, 3..5], |_: &mut RangeSet<i32>| () => &[0..5],
+        keep_gaps: [0..3, 4..5], |_: &mut RangeSet<i32>| () => &[0..3, 4..5],
+        drop_empty: [3..3, 5..1], |_: &mut RangeSet<i32>| () => &[] as &[Range<i32>],
+        insert_bridges: [0..2, 5..7, 9..10], |s: &mut RangeSet<i32>| s.insert(2..5) => &[0..7, 9..10],
+        insert_negative: [0..2], |s: &mut RangeSet<i32>| s.insert(-5..-3) => &[-5..-3, 0..2],
+        remove_middle: [0..10], |s: &mut RangeSet<i32>| s.remove(3..5) => &[0..3, 5..10],
+        remove_across: [0..3, 5..8, 10..12], |s: &mut RangeSet<i32>| s.remove(2..11) => &[0..2, 11..12],
+        remove_outside: [0..3], |s: &mut RangeSet<i32>| s.remove(3..6) => &[0..3],
+    );
+
+    #[test]
+    fn set_queries() {
+        let set = RangeSet::from_iter([0_u64..3, 10..20]);
+
+        assert_eq!(set.len(), 13);
+        assert_eq!((set.min(), set.max()), (Some(0), Some(19)));
+        assert!(set.contains(2) && set.contains(10) && !set.contains(3) && !set.contains(20));
+    }
+
+    #[test]
+    fn map_insert_splits() {
+        let mut map = RangeMap::from_iter([(0_u32..10, 'a')]);
+        map.insert(3..5, 'b');
+
+        assert_eq!(
+            map.iter().map(|(r, &v)| (r.clone(), v)).collect::<Vec<_>>(),
+            vec![(0..3, 'a'), (3..5, 'b'), (5..10, 'a')]
+        );
+
+        map.insert(3..5, 'a');
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn offset_compose() {
+        // The almanac's seed-to-soil and soil-to-fertilizer maps
+        let mut seed_to_soil = RangeMap::new();
+        seed_to_soil.insert_offset(98_u64..100, 50);
+        seed_to_soil.insert_offset(50..98, 52);
+
+        let mut soil_to_fertilizer = RangeMap::new();
+        soil_to_fertilizer.insert_offset(15_u64..52, 0);
+        soil_to_fertilizer.insert_offset(52..54, 37);
+        soil_to_fertilizer.insert_offset(0..15, 39);
+
+        let seed_to_fertilizer = seed_to_soil.compose(&soil_to_fertilizer);
+
+        assert_eq!(seed_to_fertilizer.apply(98), Some(35));
+        assert_eq!(seed_to_fertilizer.apply(50), Some(37));
+        assert_eq!(seed_to_fertilizer.apply(51), Some(38));
+        assert_eq!(seed_to_fertilizer.apply(52), None);
+        assert_eq!(seed_to_fertilizer.domain().ranges(), &[50..52, 98..100]);
+    }
+
+    fn ranges() -> impl Strategy<Value = Vec<Range<u8>>> {
+        prop::collection::vec((0_u8..100, 0_u8..20).prop_map(|(s, l)| s..s + l), 0..8)
+    }
+
+    fn model(ranges: &[Range<u8>]) -> BTreeSet<u8> {
+        ranges.iter().flat_map(Clone::clone).collect()
+    }
+
+    fn values(set: &RangeSet<u8>) -> BTreeSet<u8> {
+        model(set.ranges())
+    }
+
+    fn offset_map() -> impl Strategy<Value = RangeMap<u8, u8>> {
+        prop::collection::vec((0_u8..100, 0_u8..20, 0_u8..100), 0..6).prop_map(|pieces| {
+            let mut map = RangeMap::new();
+
+            for (start, len, dest) in pieces {
+                map.insert_offset(start..start + len, dest);
+            }
+
+            map
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn set_is_canonical(ranges in ranges()) {
+            let set = ranges.iter().cloned().collect::<RangeSet<u8>>();
+
+            prop_assert_eq!(values(&set), model(&ranges));
+            prop_assert_eq!(usize::from(set.len()), model(&ranges).len());
+            prop_assert!(set.ranges().iter().all(|r| !r.is_empty()));
+            prop_assert!(set.ranges().windows(2).all(|w| w[0].end < w[1].start));
+
+            let mut inserted = RangeSet::new();
+            for range in &ranges {
+                inserted.insert(range.clone());
+            }
+            prop_assert_eq!(inserted, set);
+        }
+
+        #[test]
+        fn set_operations(a in ranges(), b in ranges()) {
+            let (set_a, set_b) = (RangeSet::from_iter(a.clone()), RangeSet::from_iter(b.clone()));
+            let (model_a, model_b) = (model(&a), model(&b));
+
+            prop_assert_eq!(values(&set_a.union(&set_b)), &model_a | &model_b);
+            prop_assert_eq!(values(&set_a.intersection(&set_b)), &model_a & &model_b);
+            prop_assert_eq!(values(&set_a.difference(&set_b)), &model_a - &model_b);
+
+            for x in 0..=u8::MAX {
+                prop_assert_eq!(set_a.contains(x), model_a.contains(&x));
+            }
+        }
+
+        #[test]
+        fn map_insert_overwrites(pieces in prop::collection::vec((0_u8..100, 0_u8..20, 0_u8..3), 0..8)) {
+            let map = pieces
+                .iter()
+                .map(|&(start, len, value)| (start..start + len, value))
+                .collect::<RangeMap<u8, u8>>();
+
+            let mut model = BTreeMap::new();
+            for &(start, len, value) in &pieces {
+                for key in start..start + len {
+                    model.insert(key, value);
+                }
+            }
+
+            for key in 0..=u8::MAX {
+                prop_assert_eq!(map.get(key), model.get(&key));
+            }
+            prop_assert!(map.iter().zip(map.iter().skip(1)).all(|((a, x), (b, y))| a.end < b.start || x != y));
+        }
+
+        #[test]
+        fn compose_applies_in_order(a in offset_map(), b in offset_map()) {
+            let composed = a.compose(&b);
+
+            for key in 0..=u8::MAX {
+                prop_assert_eq!(composed.apply(key), a.apply(key).and_then(|k| b.apply(k)));
+            }
+        }
+    }
+}
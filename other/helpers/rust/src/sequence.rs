@@ -0,0 +1,345 @@
+use std::{
+    fmt,
+    ops::{Add, Div, Mul, Sub},
+};
+
+/// The rows of the difference table of `terms`, starting with `terms` itself
+/// and stopping after the first row that's all zeros (or has one value left)
+#[must_use]
+pub fn difference_table(terms: &[i64]) -> Vec<Vec<i64>> {
+    let mut table = vec![terms.to_vec()];
+
+    while let Some(row) = table.last() {
+        if row.len() <= 1 || row.iter().all(|&term| term == 0) {
+            break;
+        }
+
+        let next = row.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        table.push(next);
+    }
+
+    table
+}
+
+/// The term after the last of `terms`, assuming they're a polynomial sequence
+/// of degree less than `terms.len()`
+#[must_use]
+pub fn extrapolate_forward(terms: &[i64]) -> i64 {
+    difference_table(terms)
+        .iter()
+        .filter_map(|row| row.last())
+        .sum()
+}
+
+/// The term before the first of `terms`, assuming they're a polynomial
+/// sequence of degree less than `terms.len()`
+#[must_use]
+pub fn extrapolate_backward(terms: &[i64]) -> i64 {
+    difference_table(terms)
+        .iter()
+        .rev()
+        .filter_map(|row| row.first())
+        .fold(0, |below, &first| first - below)
+}
+
+/// A polynomial sequence, defined from its first terms by Newton's forward
+/// difference formula so that any term can be found without stepping to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sequence {
+    // The first value of each row of the difference table, without the zeros
+    leading: Vec<i128>,
+}
+
+impl Sequence {
+    /// The lowest degree polynomial sequence starting with `terms`
+    #[must_use]
+    pub fn from_terms(terms: &[i64]) -> Sequence {
+        let mut leading = difference_table(terms)
+            .iter()
+            .filter_map(|row| row.first())
+            .map(|&first| i128::from(first))
+            .collect::<Vec<_>>();
+
+        while leading.last() == Some(&0) {
+            leading.pop();
+        }
+
+        Sequence { leading }
+    }
+
+    /// The degree of the polynomial, or `None` if every term is zero
+    #[must_use]
+    pub fn degree(&self) -> Option<usize> {
+        self.leading.len().checked_sub(1)
+    }
+
+    /// The term at index `n`, where the first given term is index 0 and
+    /// negative indexes run backwards from it
+    ///
+    /// # Panics
+    /// If the term or one of the binomial coefficients for it overflows an
+    /// `i128`
+    #[must_use]
+    pub fn nth_term(&self, n: i128) -> i128 {
+        let mut binomial = 1_i128;
+        let mut term = 0_i128;
+
+        for (k, &difference) in (0_i128..).zip(&self.leading) {
+            term = binomial
+                .checked_mul(difference)
+                .and_then(|product| term.checked_add(product))
+                .expect("sequence term overflowed i128");
+
+            // C(n, k + 1) = C(n, k) * (n - k) / (k + 1), which divides exactly
+            binomial = binomial
+                .checked_mul(n - k)
+                .expect("binomial coefficient overflowed i128")
+                / (k + 1);
+        }
+
+        term
+    }
+}
+
+/// An exact fraction, always stored in lowest terms with a positive
+/// denominator
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    /// # Panics
+    /// If `denominator` is zero
+    #[must_use]
+    pub fn new(numerator: i128, denominator: i128) -> Rational {
+        assert!(denominator != 0, "rational with a zero denominator");
+
+        let divisor = i128::try_from(gcd(numerator.unsigned_abs(), denominator.unsigned_abs()))
+            .unwrap()
+            * denominator.signum();
+
+        Rational {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    #[must_use]
+    pub fn numerator(self) -> i128 {
+        self.numerator
+    }
+
+    #[must_use]
+    pub fn denominator(self) -> i128 {
+        self.denominator
+    }
+
+    /// The value as an integer, or `None` if it isn't one
+    #[must_use]
+    pub fn to_integer(self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Rational::new(value, 1)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Rational) -> Rational {
+        Rational::new(
+            self.numerator * rhs.denominator + rhs.numerator * self.denominator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Rational) -> Rational {
+        self + Rational::new(-rhs.numerator, rhs.denominator)
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Rational) -> Rational {
+        Rational::new(
+            self.numerator * rhs.numerator,
+            self.denominator * rhs.denominator,
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    /// # Panics
+    /// If `rhs` is zero
+    fn div(self, rhs: Rational) -> Rational {
+        Rational::new(
+            self.numerator * rhs.denominator,
+            self.denominator * rhs.numerator,
+        )
+    }
+}
+
+/// The value at `x` of the lowest degree polynomial through `points`, using
+/// Newton's divided differences, or `None` if two points share an `x`
+#[must_use]
+pub fn interpolate(points: &[(i64, i64)], x: i64) -> Option<Rational> {
+    let xs = points
+        .iter()
+        .map(|&(x, _)| i128::from(x))
+        .collect::<Vec<_>>();
+    let mut coefficients = points
+        .iter()
+        .map(|&(_, y)| Rational::from(i128::from(y)))
+        .collect::<Vec<_>>();
+
+    // After step `j`, `coefficients[i]` is the divided difference over
+    // points `i - j..=i`
+    for j in 1..points.len() {
+        for i in (j..points.len()).rev() {
+            let width = xs[i] - xs[i - j];
+
+            if width == 0 {
+                return None;
+            }
+
+            coefficients[i] = (coefficients[i] - coefficients[i - 1]) / Rational::from(width);
+        }
+    }
+
+    // Horner's method on the Newton form
+    let x = i128::from(x);
+    let value = coefficients
+        .iter()
+        .zip(&xs)
+        .rev()
+        .fold(Rational::from(0), |acc, (&coefficient, &xi)| {
+            acc * Rational::from(x - xi) + coefficient
+        });
+
+    Some(value)
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    macro_rules! extrapolate_test {
+        ($suite:ident, $($name:ident: $terms:expr => $forward:expr, $backward:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        let terms: &[i64] = &$terms;
+
+                        assert_eq!(extrapolate_forward(terms), $forward);
+                        assert_eq!(extrapolate_backward(terms), $backward);
+                    }
+                )*
+            }
+        }
+    }
+
+    // The first three are the OASIS report from 2023 day 9
+    extrapolate_test!(extrapolate_tests,
+        linear: [0, 3, 6, 9, 12, 15] => 18, -3,
+        triangular: [1, 3, 6, 10, 15, 21] => 28, 0,
+        cubic: [10, 13, 16, 21, 30, 45] => 68, 5,
+        constant: [7, 7, 7] => 7, 7,
+        single: [4] => 4, 4,
+        empty: [] => 0, 0,
+        negative: [-2, -5, -10, -17] => -26, -1,
+    );
+
+    #[test]
+    fn difference_table_rows() {
+        assert_eq!(
+            difference_table(&[1, 3, 6, 10]),
+            vec![vec![1, 3, 6, 10], vec![2, 3, 4], vec![1, 1], vec![0]]
+        );
+    }
+
+    #[test]
+    fn nth_term() {
+        let squares = Sequence::from_terms(&[0, 1, 4, 9]);
+
+        assert_eq!(squares.degree(), Some(2));
+        assert_eq!(squares.nth_term(-3), 9);
+        assert_eq!(
+            squares.nth_term(1_000_000_000_000),
+            1_000_000_000_000_000_000_000_000
+        );
+        assert_eq!(Sequence::from_terms(&[0, 0]).degree(), None);
+        assert_eq!(Sequence::from_terms(&[0, 0]).nth_term(5), 0);
+    }
+
+    #[test]
+    fn interpolate_uneven_points() {
+        // y = x² / 2, which isn't an integer at odd x
+        let points = [(0, 0), (2, 2), (6, 18)];
+
+        assert_eq!(interpolate(&points, 4), Some(Rational::from(8)));
+        assert_eq!(interpolate(&points, 3), Some(Rational::new(9, 2)));
+        assert_eq!(interpolate(&points, -1).unwrap().to_string(), "1/2");
+        assert_eq!(interpolate(&[(1, 1), (1, 2)], 0), None);
+        assert_eq!(interpolate(&[], 0), Some(Rational::from(0)));
+    }
+
+    #[test]
+    fn rational_normalizes() {
+        let half = Rational::new(3, -6);
+
+        assert_eq!((half.numerator(), half.denominator()), (-1, 2));
+        assert_eq!((half + half).to_integer(), Some(-1));
+        assert_eq!(half * half / half - half, Rational::from(0));
+    }
+
+    proptest! {
+        #[test]
+        fn nth_term_matches_polynomial(coefficients in prop::collection::vec(-10_i64..10, 0..5), n in -50_i64..50) {
+            let f = |x: i64| coefficients.iter().rev().fold(0, |acc, &c| acc * x + c);
+            let terms = (0..6).map(f).collect::<Vec<_>>();
+            let sequence = Sequence::from_terms(&terms);
+            let points = (0..6).map(|x| (x * 3 - 7, f(x * 3 - 7))).collect::<Vec<_>>();
+
+            prop_assert_eq!(sequence.nth_term(i128::from(n)), i128::from(f(n)));
+            prop_assert_eq!(interpolate(&points, n), Some(Rational::from(i128::from(f(n)))));
+            prop_assert_eq!(extrapolate_forward(&terms), f(6));
+            prop_assert_eq!(extrapolate_backward(&terms), f(-1));
+        }
+    }
+}
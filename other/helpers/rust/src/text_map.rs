@@ -1,39 +1,234 @@
-use crate::{neighbors::Grid2D, Error};
+use std::{borrow::Cow, fmt, mem};
 
+use crate::{
+    map::Map2D,
+    neighbors::{Grid2D, Grid2DMut, Grid2DRefMut},
+    Error,
+};
+
+/// The line terminator that followed a row in the source text
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    None,
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::None => "",
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// A grid of bytes backed by the lines of a text input.
+///
+/// Rows borrow from the source text until they're written to, at which point
+/// only the modified row is copied. Line endings are kept so the map can be
+/// written back out exactly as it was read.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct TextMap<'a> {
-    map: Vec<&'a [u8]>,
+    map: Vec<Cow<'a, [u8]>>,
+    line_endings: Vec<LineEnding>,
     width: usize,
 }
 
 impl<'a> TextMap<'a> {
+    /// Parses a map using the default [`TextMapOptions`]
+    ///
     /// # Errors
     /// * `Error::InconsistentMapWidth` - map has inconsistent line length (map width)
-    pub fn parse(source: &str) -> Result<TextMap, Error> {
-        let mut map = Vec::new();
-        let mut width = None;
+    pub fn parse(source: &'a str) -> Result<TextMap<'a>, Error> {
+        TextMapOptions::default().parse(source)
+    }
+
+    /// Copies any rows still borrowed from the source text, detaching the map
+    /// from its source
+    #[must_use]
+    pub fn into_owned(self) -> TextMap<'static> {
+        TextMap {
+            map: self
+                .map
+                .into_iter()
+                .map(|row| Cow::Owned(row.into_owned()))
+                .collect(),
+            line_endings: self.line_endings,
+            width: self.width,
+        }
+    }
+
+    #[must_use]
+    pub fn row(&self, y: usize) -> Option<&[u8]> {
+        self.map.get(y).map(AsRef::as_ref)
+    }
 
-        for line in source.lines() {
-            let line = line.as_bytes();
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        self.map.iter().map(AsRef::as_ref)
+    }
+
+    #[must_use]
+    pub fn line_ending(&self, y: usize) -> Option<LineEnding> {
+        self.line_endings.get(y).copied()
+    }
+}
 
-            if let Some(width) = width {
-                if line.len() != width {
-                    return Err(Error::InconsistentMapWidth);
+/// Options for parsing text into a [`TextMap`].
+///
+/// The defaults match [`TextMap::parse`]: `\r\n` line endings are stripped,
+/// indentation is kept, and every row must have the same width.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(clippy::module_name_repetitions)]
+pub struct TextMapOptions {
+    fill: Option<u8>,
+    strip_cr: bool,
+    trim_indent: bool,
+}
+
+impl Default for TextMapOptions {
+    fn default() -> Self {
+        TextMapOptions {
+            fill: None,
+            strip_cr: true,
+            trim_indent: false,
+        }
+    }
+}
+
+struct Line<'a> {
+    number: usize,
+    row: &'a [u8],
+    ending: LineEnding,
+}
+
+impl TextMapOptions {
+    #[must_use]
+    pub fn new() -> TextMapOptions {
+        TextMapOptions::default()
+    }
+
+    /// Pads rows shorter than the widest row with `fill` instead of failing
+    #[must_use]
+    pub fn pad_with(mut self, fill: u8) -> TextMapOptions {
+        self.fill = Some(fill);
+        self
+    }
+
+    /// Whether a `\r` before each line's `\n` is stripped (the default) or
+    /// kept as part of the row
+    #[must_use]
+    pub fn strip_cr(mut self, strip_cr: bool) -> TextMapOptions {
+        self.strip_cr = strip_cr;
+        self
+    }
+
+    /// Whether leading spaces and tabs are trimmed from every line, e.g. for
+    /// examples pasted into indented test strings
+    #[must_use]
+    pub fn trim_indent(mut self, trim_indent: bool) -> TextMapOptions {
+        self.trim_indent = trim_indent;
+        self
+    }
+
+    /// # Errors
+    /// * `Error::InconsistentMapWidth` - map has inconsistent line length (map
+    ///   width) and no fill byte was given
+    pub fn parse(self, source: &str) -> Result<TextMap<'_>, Error> {
+        self.build(self.lines(source).collect())
+    }
+
+    /// Parses a sequence of maps separated by one or more blank lines
+    ///
+    /// # Errors
+    /// * `Error::InconsistentMapWidth` - a map has inconsistent line length
+    ///   (map width) and no fill byte was given
+    pub fn parse_many(self, source: &str) -> Result<Vec<TextMap<'_>>, Error> {
+        let mut maps = Vec::new();
+        let mut lines = Vec::new();
+
+        for line in self.lines(source) {
+            if !line.row.is_empty() {
+                lines.push(line);
+            } else if !lines.is_empty() {
+                maps.push(self.build(mem::take(&mut lines))?);
+            }
+        }
+
+        if !lines.is_empty() {
+            maps.push(self.build(lines)?);
+        }
+
+        Ok(maps)
+    }
+
+    fn lines(self, source: &str) -> impl Iterator<Item = Line<'_>> {
+        source
+            .split_inclusive('\n')
+            .enumerate()
+            .map(move |(i, line)| {
+                let (mut line, line_ending) = match line.strip_suffix('\n') {
+                    Some(line) if self.strip_cr => match line.strip_suffix('\r') {
+                        Some(line) => (line, LineEnding::CrLf),
+                        None => (line, LineEnding::Lf),
+                    },
+                    Some(line) => (line, LineEnding::Lf),
+                    None => (line, LineEnding::None),
+                };
+
+                if self.trim_indent {
+                    line = line.trim_start_matches([' ', '\t']);
+                }
+
+                Line {
+                    number: i + 1,
+                    row: line.as_bytes(),
+                    ending: line_ending,
                 }
+            })
+    }
+
+    fn build(self, lines: Vec<Line<'_>>) -> Result<TextMap<'_>, Error> {
+        let width = match self.fill {
+            Some(_) => lines.iter().map(|line| line.row.len()).max(),
+            None => lines.first().map(|line| line.row.len()),
+        }
+        .unwrap_or(0);
+
+        let mut map = Vec::with_capacity(lines.len());
+        let mut line_endings = Vec::with_capacity(lines.len());
+
+        for line in lines {
+            let row = if line.row.len() == width {
+                Cow::Borrowed(line.row)
+            } else if let Some(fill) = self.fill {
+                let mut row = line.row.to_vec();
+                row.resize(width, fill);
+
+                Cow::Owned(row)
             } else {
-                width = Some(line.len());
-            }
+                return Err(Error::InconsistentMapWidth {
+                    line: line.number,
+                    expected: width,
+                    found: line.row.len(),
+                });
+            };
 
-            map.push(line);
+            map.push(row);
+            line_endings.push(line.ending);
         }
 
         Ok(TextMap {
             map,
-            width: width.unwrap_or(0),
+            line_endings,
+            width,
         })
     }
 }
 
-impl<'a> Grid2D for TextMap<'a> {
+impl Grid2D for TextMap<'_> {
     type Item = u8;
 
     fn width(&self) -> usize {
@@ -55,3 +250,153 @@ impl<'a> Grid2D for TextMap<'a> {
             .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, byte)| (x, y, byte)))
     }
 }
+
+impl Grid2DMut for TextMap<'_> {
+    fn set(&mut self, x: usize, y: usize, item: u8) -> Option<u8> {
+        let space = self.get_mut(x, y)?;
+
+        Some(mem::replace(space, item))
+    }
+}
+
+impl Grid2DRefMut for TextMap<'_> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut u8> {
+        let row = self.map.get_mut(y)?;
+
+        // Avoid copying a borrowed row just to find out the index is invalid
+        if x >= row.len() {
+            return None;
+        }
+
+        row.to_mut().get_mut(x)
+    }
+
+    fn iter_mut(&mut self) -> impl Iterator<Item = (usize, usize, &mut u8)> {
+        self.map.iter_mut().enumerate().flat_map(|(y, row)| {
+            row.to_mut()
+                .iter_mut()
+                .enumerate()
+                .map(move |(x, byte)| (x, y, byte))
+        })
+    }
+}
+
+impl fmt::Display for TextMap<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (row, line_ending) in self.map.iter().zip(&self.line_endings) {
+            f.write_str(&String::from_utf8_lossy(row))?;
+            f.write_str(line_ending.as_str())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Map2D<u8>> for TextMap<'static> {
+    fn from(map: Map2D<u8>) -> Self {
+        let rows = map
+            .rows()
+            .map(|row| Cow::Owned(row.to_vec()))
+            .collect::<Vec<_>>();
+
+        TextMap {
+            line_endings: vec![LineEnding::Lf; rows.len()],
+            map: rows,
+            width: map.width(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! round_trip_test {
+        ($suite:ident, $($name:ident: $input:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        let map = TextMap::parse($input).unwrap();
+
+                        assert_eq!(map.to_string(), $input);
+                    }
+                )*
+            }
+        }
+    }
+
+    round_trip_test!(round_trip_tests,
+        empty: "",
+        trailing_newline: "ab\ncd\n",
+        no_trailing_newline: "ab\ncd",
+        crlf: "ab\r\ncd\r\n",
+        mixed_line_endings: "ab\r\ncd\nef",
+        empty_lines: "\n\n",
+    );
+
+    #[test]
+    fn set_only_copies_modified_row() {
+        let mut map = TextMap::parse("S.\n..\n").unwrap();
+
+        assert_eq!(map.set(0, 0, b'F'), Some(b'S'));
+        assert_eq!(map.set(2, 0, b'F'), None);
+
+        assert!(matches!(map.map[0], Cow::Owned(_)));
+        assert!(matches!(map.map[1], Cow::Borrowed(_)));
+        assert_eq!(map.to_string(), "F.\n..\n");
+    }
+
+    #[test]
+    fn map_2d_conversion() {
+        let map = TextMap::parse("#.\n.#\n").unwrap();
+        let map_2d = Map2D::from(map.clone());
+
+        assert_eq!(map_2d.get(1, 1), Some(&b'#'));
+        assert_eq!(TextMap::from(map_2d), map);
+    }
+
+    macro_rules! options_test {
+        ($suite:ident, $($name:ident: $options:expr, $input:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        let output = $options
+                            .parse_many($input)
+                            .map(|maps| maps.iter().map(ToString::to_string).collect::<Vec<_>>());
+
+                        assert_eq!(output, $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    options_test!(options_tests,
+        default_strips_cr: TextMapOptions::new(), "ab\r\ncd\r\n" => Ok(vec!["ab\r\ncd\r\n".to_owned()]),
+        keep_cr: TextMapOptions::new().strip_cr(false), "ab\r\ncd\n" => Err(Error::InconsistentMapWidth {
+            line: 2,
+            expected: 3,
+            found: 2,
+        }),
+        ragged: TextMapOptions::new(), "ab\nc\n" => Err(Error::InconsistentMapWidth {
+            line: 2,
+            expected: 2,
+            found: 1,
+        }),
+        ragged_padded: TextMapOptions::new().pad_with(b' '), "a\nbcd\nef\n" => Ok(vec!["a  \nbcd\nef \n".to_owned()]),
+        trim_indent: TextMapOptions::new().trim_indent(true), "ab\n    cd\n\tef\n" => Ok(vec!["ab\ncd\nef\n".to_owned()]),
+        multiple_maps: TextMapOptions::new(), "\nab\ncd\n\n\nefg\n\n" => Ok(vec!["ab\ncd\n".to_owned(), "efg\n".to_owned()]),
+        multiple_maps_error_line: TextMapOptions::new(), "ab\n\ncde\nfg\n" => Err(Error::InconsistentMapWidth {
+            line: 4,
+            expected: 3,
+            found: 2,
+        }),
+        no_maps: TextMapOptions::new(), "\n\n" => Ok(vec![]),
+    );
+}
@@ -0,0 +1,205 @@
+use std::{
+    fmt::{self, Write},
+    fs, io,
+    path::Path,
+};
+
+use crate::error::Error;
+
+/// The file in a day's directory recording its correct answers, as the
+/// `Part N: answer` lines a solution prints
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// The file in a day's directory recording answers the site rejected, so
+/// they're never submitted again
+pub const REJECTED_FILE: &str = "rejected.txt";
+
+/// What the site said about a wrong answer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Hint::TooHigh => "too high",
+            Hint::TooLow => "too low",
+        })
+    }
+}
+
+/// A wrong answer to a part
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rejection {
+    pub part: u8,
+    pub answer: String,
+    pub hint: Option<Hint>,
+}
+
+/// The recorded answers for each part of the day in `day_dir`
+///
+/// # Errors
+/// * `Error::Io` - if the answers file exists but can't be read
+pub fn read(day_dir: &Path) -> Result<[Option<String>; 2], Error> {
+    Ok(parse(&read_file(&day_dir.join(ANSWERS_FILE))?))
+}
+
+/// Records the correct answer to `part`, keeping the other part's
+///
+/// # Errors
+/// * `Error::Io` - if the answers file exists but can't be read
+/// * `Error::Write` - if the answers file can't be written
+pub fn record(day_dir: &Path, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = read(day_dir)?;
+    answers[usize::from(part - 1)] = Some(answer.to_owned());
+
+    let text = answers
+        .iter()
+        .zip(1..)
+        .filter_map(|(answer, part)| Some(format!("Part {part}: {}\n", answer.as_ref()?)))
+        .collect::<String>();
+
+    write_file(&day_dir.join(ANSWERS_FILE), &text)
+}
+
+/// Every answer the site rejected for the day in `day_dir`
+///
+/// # Errors
+/// * `Error::Io` - if the rejected file exists but can't be read
+pub fn read_rejected(day_dir: &Path) -> Result<Vec<Rejection>, Error> {
+    Ok(parse_rejected(&read_file(&day_dir.join(REJECTED_FILE))?))
+}
+
+/// Adds a rejected answer, as `Part N: answer (hint)`
+///
+/// # Errors
+/// * `Error::Io` - if the rejected file exists but can't be read
+/// * `Error::Write` - if the rejected file can't be written
+pub fn record_rejected(day_dir: &Path, rejection: &Rejection) -> Result<(), Error> {
+    let path = day_dir.join(REJECTED_FILE);
+    let mut text = read_file(&path)?;
+
+    write!(text, "Part {}: {}", rejection.part, rejection.answer).unwrap();
+
+    if let Some(hint) = rejection.hint {
+        write!(text, " ({hint})").unwrap();
+    }
+
+    text.push('\n');
+
+    write_file(&path, &text)
+}
+
+/// The file's contents, or nothing if it doesn't exist
+fn read_file(path: &Path) -> Result<String, Error> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(text),
+        Err(source) if source.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(source) => Err(Error::Io {
+            path: path.to_owned(),
+            source,
+        }),
+    }
+}
+
+fn write_file(path: &Path, text: &str) -> Result<(), Error> {
+    let write_error = |source| Error::Write {
+        path: path.to_owned(),
+        source,
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(write_error)?;
+    }
+
+    fs::write(path, text).map_err(write_error)
+}
+
+fn parse(text: &str) -> [Option<String>; 2] {
+    [1, 2].map(|part| {
+        let prefix = format!("Part {part}: ");
+
+        text.lines()
+            .find_map(|line| line.strip_prefix(&prefix))
+            .map(|answer| answer.trim().to_owned())
+    })
+}
+
+fn parse_rejected(text: &str) -> Vec<Rejection> {
+    text.lines()
+        .filter_map(|line| {
+            let (part, answer) = line.strip_prefix("Part ")?.split_once(": ")?;
+            let part = part.parse().ok()?;
+
+            let (answer, hint) = [Hint::TooHigh, Hint::TooLow]
+                .into_iter()
+                .find_map(|hint| {
+                    let answer = answer.strip_suffix(&format!(" ({hint})"))?;
+                    Some((answer, Some(hint)))
+                })
+                .unwrap_or((answer, None));
+
+            Some(Rejection {
+                part,
+                answer: answer.trim().to_owned(),
+                hint,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_answers() {
+        assert_eq!(
+            parse("Part 1: 54239\nPart 2: 55343\n"),
+            [Some("54239".to_owned()), Some("55343".to_owned())]
+        );
+        assert_eq!(parse("Part 2: abc\n"), [None, Some("abc".to_owned())]);
+        assert_eq!(read(Path::new("/no/such/day")).unwrap(), [None, None]);
+    }
+
+    #[test]
+    fn records_answers_and_rejections() {
+        let dir = std::env::temp_dir().join(format!("aoc_runner_answers_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        record(&dir, 2, "55343").unwrap();
+        record(&dir, 1, "54239").unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.join(ANSWERS_FILE)).unwrap(),
+            "Part 1: 54239\nPart 2: 55343\n"
+        );
+
+        let rejections = [
+            Rejection {
+                part: 1,
+                answer: "100".to_owned(),
+                hint: Some(Hint::TooHigh),
+            },
+            Rejection {
+                part: 2,
+                answer: "abc".to_owned(),
+                hint: None,
+            },
+        ];
+
+        for rejection in &rejections {
+            record_rejected(&dir, rejection).unwrap();
+        }
+
+        assert_eq!(
+            fs::read_to_string(dir.join(REJECTED_FILE)).unwrap(),
+            "Part 1: 100 (too high)\nPart 2: abc\n"
+        );
+        assert_eq!(read_rejected(&dir).unwrap(), rejections);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
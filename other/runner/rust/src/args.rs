@@ -0,0 +1,388 @@
+use std::{path::PathBuf, time::Duration};
+
+use aoc_helpers::answer::Format;
+
+use crate::{error::Error, http, leaderboard::Source, pool};
+
+pub const USAGE: &str = "\
+Usage: aoc_runner <command> [options]
+
+Commands:
+  run [year|all] [day|all]      build and run solutions, all of them by default
+  compare [year|all] [day|all]  run every language's solution for each day and
+                                check that their answers agree
+  submit <year> <day> <part> [answer]
+                                submit an answer, by default the Rust
+                                solution's, unless it's known to be wrong
+  puzzle <year> <day>           download a day's puzzle as Markdown, and its
+                                code blocks as example inputs
+  leaderboard <path>|<year> <id>
+                                print scores, daily rankings and star timelines
+                                for a private leaderboard, from a JSON file or
+                                fetched (at most every 15 minutes)
+  wait <year> <day>             count down to a day's unlock at midnight in New
+                                York, then set it up from the Rust template and
+                                download its input
+  progress                      regenerate the progress tables in the READMEs
+                                and list days missing examples or write-ups
+
+Options:
+  --timeout <seconds>  wall-clock limit for each part (default: 10)
+  --jobs <n>           how many parts to run at once (default: one per core)
+  --root <path>        the repository to find solutions in
+  --format <fmt>       print results as a text table, json (one object per
+                       line) or tsv (default: text)
+  --times <path>       for progress, take part times from the output of
+                       `run --format tsv` (default: keep the ones already
+                       in the READMEs)
+  --day <n>            for leaderboard, only show one day
+  --refresh            for puzzle and leaderboard, download the page again
+  --base-url <url>     where to submit answers and download puzzles from
+                       (default: https://adventofcode.com)";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        year: Option<u32>,
+        day: Option<u32>,
+    },
+    Compare {
+        year: Option<u32>,
+        day: Option<u32>,
+    },
+    Progress {
+        times: Option<PathBuf>,
+    },
+    Puzzle {
+        year: u32,
+        day: u32,
+        refresh: bool,
+    },
+    Wait {
+        year: u32,
+        day: u32,
+    },
+    Leaderboard {
+        source: Source,
+        day: Option<u32>,
+        refresh: bool,
+    },
+    Submit {
+        year: u32,
+        day: u32,
+        part: u8,
+        answer: Option<String>,
+    },
+    Help,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Args {
+    pub command: Command,
+    pub timeout: Duration,
+    pub jobs: usize,
+    pub root: PathBuf,
+    pub format: Format,
+    pub base_url: String,
+}
+
+impl Args {
+    /// Parses the arguments after the program name
+    ///
+    /// # Errors
+    /// * `Error::Usage` - if the arguments don't match the usage
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Args, Error> {
+        let mut timeout = Duration::from_secs(10);
+        let mut jobs = pool::default_threads();
+        let mut root = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../../.."));
+        let mut format = Format::Text;
+        let mut times = None;
+        let mut refresh = false;
+        let mut day = None;
+        let mut base_url = http::DEFAULT_BASE_URL.to_owned();
+        let mut positional = Vec::new();
+
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| Error::Usage(format!("{arg} needs a value")))
+            };
+
+            match arg.as_str() {
+                "--timeout" => {
+                    let seconds = value()?;
+
+                    timeout = seconds
+                        .parse()
+                        .ok()
+                        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                        .ok_or_else(|| Error::Usage(format!("invalid timeout {seconds:?}")))?;
+                }
+                "--jobs" => {
+                    let n = value()?;
+
+                    jobs = n
+                        .parse()
+                        .ok()
+                        .filter(|&n| n > 0)
+                        .ok_or_else(|| Error::Usage(format!("invalid job count {n:?}")))?;
+                }
+                "--root" => root = PathBuf::from(value()?),
+                "--times" => times = Some(PathBuf::from(value()?)),
+                "--base-url" => base_url = value()?,
+                "--refresh" => refresh = true,
+                "--day" => day = Some(required_number(Some(&value()?), "day")?),
+                "--format" => {
+                    format =
+                        value()?
+                            .parse()
+                            .map_err(|error: aoc_helpers::answer::UnknownFormat| {
+                                Error::Usage(error.to_string())
+                            })?;
+                }
+                "-h" | "--help" => positional = vec!["help".to_owned()],
+                // Everything after `--` is positional, even if it starts with `-`
+                "--" => positional.extend(args.by_ref()),
+                // Negative numbers, like an answer, aren't options
+                _ if arg.starts_with('-') && arg.parse::<i64>().is_err() => {
+                    return Err(Error::Usage(format!("unknown option {arg:?}")));
+                }
+                _ => positional.push(arg),
+            }
+        }
+
+        let command = match positional.first().map(String::as_str) {
+            None | Some("help") => Command::Help,
+            Some("run") => Command::Run {
+                year: optional_number(positional.get(1), "year")?,
+                day: optional_number(positional.get(2), "day")?,
+            },
+            Some("compare") => Command::Compare {
+                year: optional_number(positional.get(1), "year")?,
+                day: optional_number(positional.get(2), "day")?,
+            },
+            Some("progress") => Command::Progress { times },
+            Some("puzzle") => {
+                let (year, day) = puzzle_date(&positional)?;
+                Command::Puzzle { year, day, refresh }
+            }
+            Some("wait") => {
+                let (year, day) = puzzle_date(&positional)?;
+                Command::Wait { year, day }
+            }
+            Some("leaderboard") => Command::Leaderboard {
+                source: leaderboard_source(&positional[1..])?,
+                day,
+                refresh,
+            },
+            Some("submit") => {
+                let (year, day) = puzzle_date(&positional)?;
+
+                Command::Submit {
+                    year,
+                    day,
+                    part: match required_number(positional.get(3), "part")? {
+                        part @ (1 | 2) => part,
+                        part => return Err(Error::Usage(format!("invalid part {part:?}"))),
+                    },
+                    answer: positional.get(4).cloned(),
+                }
+            }
+            Some(command) => return Err(Error::Usage(format!("unknown command {command:?}"))),
+        };
+
+        Ok(Args {
+            command,
+            timeout,
+            jobs,
+            root,
+            format,
+            base_url,
+        })
+    }
+}
+
+/// A leaderboard file, or the year and id of one to fetch
+fn leaderboard_source(args: &[String]) -> Result<Source, Error> {
+    match args {
+        [path] => Ok(Source::File(PathBuf::from(path))),
+        [year, id] => Ok(Source::Fetch {
+            year: required_number(Some(year), "year")?,
+            id: required_number(Some(id), "leaderboard id")?,
+        }),
+        _ => Err(Error::Usage(
+            "leaderboard needs a file, or a year and leaderboard id".to_owned(),
+        )),
+    }
+}
+
+/// The year and day after a command, checked against the days that have
+/// puzzles: 1 to 25 of each December since 2015
+fn puzzle_date(positional: &[String]) -> Result<(u32, u32), Error> {
+    let year = required_number(positional.get(1), "year")?;
+    let day = required_number(positional.get(2), "day")?;
+
+    if year < 2015 {
+        return Err(Error::Usage(format!(
+            "invalid year {year}, the first was 2015"
+        )));
+    }
+
+    if !(1..=25).contains(&day) {
+        return Err(Error::Usage(format!("invalid day {day}, expected 1 to 25")));
+    }
+
+    Ok((year, day))
+}
+
+fn required_number<T: std::str::FromStr>(arg: Option<&String>, name: &str) -> Result<T, Error> {
+    let arg = arg.ok_or_else(|| Error::Usage(format!("missing {name}")))?;
+
+    arg.parse()
+        .map_err(|_| Error::Usage(format!("invalid {name} {arg:?}")))
+}
+
+/// A number, or `None` for `all` or when it's left out
+fn optional_number(arg: Option<&String>, name: &str) -> Result<Option<u32>, Error> {
+    match arg.map(String::as_str) {
+        None | Some("all") => Ok(None),
+        Some(arg) => arg
+            .parse()
+            .map(Some)
+            .map_err(|_| Error::Usage(format!("invalid {name} {arg:?}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, Error> {
+        Args::parse(args.iter().map(|&arg| arg.to_owned()))
+    }
+
+    macro_rules! command_test {
+        ($suite:ident, $($name:ident: $args:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(parse(&$args).unwrap().command, $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    command_test!(command_tests,
+        nothing: [] => Command::Help,
+        help_flag: ["run", "--help"] => Command::Help,
+        run_all: ["run"] => Command::Run { year: None, day: None },
+        run_year: ["run", "2023"] => Command::Run { year: Some(2023), day: None },
+        run_day: ["run", "2023", "08"] => Command::Run { year: Some(2023), day: Some(8) },
+        run_all_days: ["run", "all", "all"] => Command::Run { year: None, day: None },
+        progress: ["progress", "--times", "times.tsv"] => Command::Progress {
+            times: Some(PathBuf::from("times.tsv")),
+        },
+        submit: ["submit", "2023", "01", "2", "1234"] => Command::Submit {
+            year: 2023,
+            day: 1,
+            part: 2,
+            answer: Some("1234".to_owned()),
+        },
+        submit_negative: ["submit", "2023", "9", "2", "-12"] => Command::Submit {
+            year: 2023,
+            day: 9,
+            part: 2,
+            answer: Some("-12".to_owned()),
+        },
+        submit_after_separator: ["submit", "2023", "9", "1", "--", "-x"] => Command::Submit {
+            year: 2023,
+            day: 9,
+            part: 1,
+            answer: Some("-x".to_owned()),
+        },
+        submit_solution: ["submit", "2023", "1", "1"] => Command::Submit {
+            year: 2023,
+            day: 1,
+            part: 1,
+            answer: None,
+        },
+        puzzle: ["puzzle", "2023", "05", "--refresh"] => Command::Puzzle {
+            year: 2023,
+            day: 5,
+            refresh: true,
+        },
+        wait: ["wait", "2024", "01"] => Command::Wait { year: 2024, day: 1 },
+        leaderboard_file: ["leaderboard", "board.json", "--day", "3"] => Command::Leaderboard {
+            source: Source::File(PathBuf::from("board.json")),
+            day: Some(3),
+            refresh: false,
+        },
+        leaderboard_fetch: ["leaderboard", "2023", "123456"] => Command::Leaderboard {
+            source: Source::Fetch { year: 2023, id: 123_456 },
+            day: None,
+            refresh: false,
+        },
+        compare_day: ["compare", "2023", "1"] => Command::Compare { year: Some(2023), day: Some(1) },
+    );
+
+    #[test]
+    fn options() {
+        let args = parse(&[
+            "run",
+            "--timeout",
+            "0.5",
+            "--jobs",
+            "3",
+            "--root",
+            "/tmp/aoc",
+            "--format",
+            "tsv",
+        ])
+        .unwrap();
+
+        assert_eq!(args.timeout, Duration::from_millis(500));
+        assert_eq!(args.jobs, 3);
+        assert_eq!(args.root, PathBuf::from("/tmp/aoc"));
+        assert_eq!(args.format, Format::Tsv);
+    }
+
+    #[test]
+    fn invalid() {
+        let error = |args: &[&str]| parse(args).unwrap_err().to_string();
+
+        assert_eq!(error(&["run", "--jobs", "0"]), "invalid job count \"0\"");
+        assert_eq!(error(&["run", "--timeout"]), "--timeout needs a value");
+        assert_eq!(error(&["run", "twenty"]), "invalid year \"twenty\"");
+        assert_eq!(error(&["compare", "2023", "x"]), "invalid day \"x\"");
+        assert_eq!(error(&["walk"]), "unknown command \"walk\"");
+        assert_eq!(error(&["submit", "2023", "1"]), "missing part");
+        assert_eq!(
+            error(&["leaderboard"]),
+            "leaderboard needs a file, or a year and leaderboard id"
+        );
+        assert_eq!(error(&["submit", "2023", "1", "3"]), "invalid part 3");
+        assert_eq!(
+            error(&["run", "--format", "csv"]),
+            "unknown output format \"csv\", expected text, json or tsv"
+        );
+        assert_eq!(error(&["run", "-x"]), "unknown option \"-x\"");
+        assert_eq!(
+            error(&["wait", "2014", "1"]),
+            "invalid year 2014, the first was 2015"
+        );
+        assert_eq!(
+            error(&["puzzle", "2023", "0"]),
+            "invalid day 0, expected 1 to 25"
+        );
+        assert_eq!(
+            error(&["submit", "2023", "26", "1"]),
+            "invalid day 26, expected 1 to 25"
+        );
+    }
+}
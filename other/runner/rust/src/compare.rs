@@ -0,0 +1,235 @@
+use std::fmt::Write;
+
+use crate::{
+    report::{PartResult, Status},
+    solution::Language,
+};
+
+/// Whether every language got the same answer for a part
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Agree,
+    /// At least two languages solved the part with different answers
+    Mismatch,
+    /// The languages that solved the part agree, but some didn't solve it
+    Failed,
+}
+
+impl Verdict {
+    pub fn label(self) -> &'static str {
+        match self {
+            Verdict::Agree => "agree",
+            Verdict::Mismatch => "MISMATCH",
+            Verdict::Failed => "failed",
+        }
+    }
+}
+
+/// Every language's status for one part of one day
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Comparison {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    pub statuses: Vec<(Language, Status)>,
+}
+
+impl Comparison {
+    pub fn verdict(&self) -> Verdict {
+        let mut answers = self.statuses.iter().filter_map(|(_, status)| match status {
+            Status::Solved(answer) => Some(answer),
+            _ => None,
+        });
+
+        let first = answers.next();
+
+        if answers.any(|answer| Some(answer) != first) {
+            Verdict::Mismatch
+        } else if first.is_none() || self.statuses.iter().any(|(_, status)| !status.is_solved()) {
+            Verdict::Failed
+        } else {
+            Verdict::Agree
+        }
+    }
+
+    /// The shared answer and who got it, or what each language got
+    fn details(&self) -> String {
+        if self.verdict() == Verdict::Agree {
+            let languages = self
+                .statuses
+                .iter()
+                .map(|(language, _)| language.to_string())
+                .collect::<Vec<_>>();
+
+            return format!(
+                "{} ({})",
+                self.statuses[0].1.details(),
+                languages.join(", ")
+            );
+        }
+
+        self.statuses
+            .iter()
+            .map(|(language, status)| match status {
+                Status::Solved(answer) => format!("{language}: {answer}"),
+                Status::TimedOut => format!("{language}: timeout"),
+                status => format!("{language}: {} ({})", status.label(), status.details()),
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Groups part results by year, day and part, keeping the order they're first
+/// seen in
+pub fn compare(results: Vec<PartResult>) -> Vec<Comparison> {
+    let mut comparisons: Vec<Comparison> = Vec::new();
+
+    for result in results {
+        let existing = comparisons.iter_mut().find(|comparison| {
+            (comparison.year, comparison.day, comparison.part)
+                == (result.year, result.day, result.part)
+        });
+
+        match existing {
+            Some(comparison) => comparison.statuses.push((result.language, result.status)),
+            None => comparisons.push(Comparison {
+                year: result.year,
+                day: result.day,
+                part: result.part,
+                statuses: vec![(result.language, result.status)],
+            }),
+        }
+    }
+
+    comparisons
+}
+
+/// A line per part saying whether the languages agree, followed by a count of
+/// each verdict
+pub fn comparison_table(comparisons: &[Comparison]) -> String {
+    let mut table = String::new();
+
+    for comparison in comparisons {
+        writeln!(
+            table,
+            "{}/{:02} part {}  {:8}  {}",
+            comparison.year,
+            comparison.day,
+            comparison.part,
+            comparison.verdict().label(),
+            comparison.details(),
+        )
+        .unwrap();
+    }
+
+    let count = |verdict| {
+        comparisons
+            .iter()
+            .filter(|comparison| comparison.verdict() == verdict)
+            .count()
+    };
+
+    write!(
+        table,
+        "\n{}/{} parts agree",
+        count(Verdict::Agree),
+        comparisons.len()
+    )
+    .unwrap();
+
+    for verdict in [Verdict::Mismatch, Verdict::Failed] {
+        let n = count(verdict);
+
+        if n > 0 {
+            write!(table, ", {n} {}", verdict.label().to_lowercase()).unwrap();
+        }
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solved(answer: &str) -> Status {
+        Status::Solved(answer.to_owned())
+    }
+
+    fn comparison(statuses: Vec<(Language, Status)>) -> Comparison {
+        Comparison {
+            year: 2023,
+            day: 1,
+            part: 1,
+            statuses,
+        }
+    }
+
+    macro_rules! verdict_test {
+        ($suite:ident, $($name:ident: $statuses:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(comparison($statuses).verdict(), $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    verdict_test!(verdict_tests,
+        agree: vec![(Language::Rust, solved("42")), (Language::Go, solved("42"))] => Verdict::Agree,
+        single: vec![(Language::Rust, solved("42"))] => Verdict::Agree,
+        mismatch: vec![(Language::Rust, solved("42")), (Language::Go, solved("41"))] => Verdict::Mismatch,
+        mismatch_with_error: vec![
+            (Language::Rust, solved("42")),
+            (Language::Go, Status::TimedOut),
+            (Language::JavaScript, solved("41")),
+        ] => Verdict::Mismatch,
+        one_failed: vec![(Language::Rust, solved("42")), (Language::Go, Status::TimedOut)] => Verdict::Failed,
+        all_failed: vec![(Language::Rust, Status::Failed("no input".to_owned()))] => Verdict::Failed,
+    );
+
+    #[test]
+    fn groups_and_reports() {
+        let result = |day, language, part, status| PartResult {
+            year: 2023,
+            day,
+            language,
+            part,
+            status,
+            duration: None,
+            parse_duration: None,
+        };
+
+        let comparisons = compare(vec![
+            result(1, Language::Rust, 1, solved("54239")),
+            result(1, Language::Rust, 2, solved("55343")),
+            result(1, Language::Go, 1, solved("54239")),
+            result(1, Language::Go, 2, solved("55342")),
+            result(2, Language::Rust, 1, solved("2505")),
+            result(
+                2,
+                Language::TypeScript,
+                1,
+                Status::Panicked("oops".to_owned()),
+            ),
+        ]);
+
+        assert_eq!(
+            comparison_table(&comparisons),
+            [
+                "2023/01 part 1  agree     54239 (Rust, Go)",
+                "2023/01 part 2  MISMATCH  Rust: 55343; Go: 55342",
+                "2023/02 part 1  failed    Rust: 2505; TypeScript: panic (oops)",
+                "",
+                "1/3 parts agree, 1 mismatch, 1 failed",
+            ]
+            .join("\n")
+        );
+    }
+}
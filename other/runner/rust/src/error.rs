@@ -0,0 +1,45 @@
+use std::{io, path::PathBuf, time::Duration};
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("{0}")]
+    Usage(String),
+
+    #[error("couldn't read {}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+
+    #[error("couldn't write {}: {source}", path.display())]
+    Write { path: PathBuf, source: io::Error },
+
+    #[error("{} has no `{}` and `{}` markers to put the progress between", .0.display(), crate::progress::START_MARKER, crate::progress::END_MARKER)]
+    MissingMarkers(PathBuf),
+
+    #[error("no solutions found")]
+    NoSolutions,
+
+    #[error("no session cookie in {}; log into Advent of Code and paste the `session` cookie there", .0.display())]
+    NoSession(PathBuf),
+
+    #[error("request to {url} failed: {message}")]
+    Http { url: String, message: String },
+
+    #[error("part already solved with {answer}")]
+    AlreadySolved { answer: String },
+
+    #[error("{answer} is known to be wrong: {reason}")]
+    KnownWrong { answer: String, reason: String },
+
+    #[error("no puzzle description on the page for {year} day {day}")]
+    NoPuzzle { year: u32, day: u32 },
+
+    #[error("invalid leaderboard: {0}")]
+    Leaderboard(aoc_helpers::Error),
+
+    #[error("couldn't get an answer to submit: {0}")]
+    NoAnswer(String),
+
+    #[error("answered too recently, wait {}s before submitting again", .0.as_secs())]
+    Cooldown(Duration),
+}
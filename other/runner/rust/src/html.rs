@@ -0,0 +1,302 @@
+use std::fmt::Write;
+
+/// A piece of an HTML document, as far as puzzle pages need
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token<'a> {
+    Open { name: String, attributes: &'a str },
+    Close(String),
+    Text(&'a str),
+}
+
+/// Splits `html` into tags and the text between them, skipping comments
+fn tokens(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, rest)| rest);
+            continue;
+        }
+
+        let Some(tag) = rest.strip_prefix('<') else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            tokens.push(Token::Text(&rest[..end]));
+            rest = &rest[end..];
+            continue;
+        };
+
+        let (tag, after) = tag.split_once('>').unwrap_or((tag, ""));
+        rest = after;
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim().to_ascii_lowercase()));
+        } else {
+            let tag = tag.trim_end_matches('/');
+            let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+
+            tokens.push(Token::Open {
+                name: name.to_ascii_lowercase(),
+                attributes,
+            });
+        }
+    }
+
+    tokens
+}
+
+/// The value of the attribute `name` in a tag's attributes
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let (_, rest) = attributes.split_once(&format!("{name}="))?;
+    let quote = rest.chars().next().filter(|&c| c == '"' || c == '\'')?;
+    let (value, _) = rest[1..].split_once(quote)?;
+
+    Some(decode_entities(value))
+}
+
+/// Replaces the character references puzzle pages use with what they stand for
+pub fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                entity => {
+                    let number = entity.strip_prefix('#')?;
+                    let code = match number.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => number.parse().ok()?,
+                    };
+
+                    char::from_u32(code)?
+                }
+            };
+
+            Some((c, end + 1))
+        });
+
+        let (c, len) = decoded.unwrap_or(('&', 1));
+        out.push(c);
+        rest = &rest[len..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// The HTML inside each `<tag>` element, which mustn't nest
+pub fn elements<'a>(html: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+    let mut elements = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find(&open) {
+        let Some(content) = rest[start..].split_once('>').map(|(_, content)| content) else {
+            break;
+        };
+        let Some((element, after)) = content.split_once(&close) else {
+            break;
+        };
+
+        elements.push(element);
+        rest = after;
+    }
+
+    elements
+}
+
+/// The text of every `<pre><code>` block, in order
+pub fn code_blocks(html: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut block = None;
+
+    for token in tokens(html) {
+        match token {
+            Token::Open { name, .. } if name == "pre" => block = Some(String::new()),
+            Token::Close(name) if name == "pre" => blocks.extend(block.take()),
+            Token::Text(text) => {
+                if let Some(block) = &mut block {
+                    block.push_str(&decode_entities(text));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+/// Converts the markup puzzle descriptions use to Markdown. Emphasis inside
+/// code is dropped, since Markdown can't show it.
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut heading = None;
+    let mut links = Vec::new();
+
+    for token in tokens(html) {
+        match token {
+            Token::Open { name, attributes } => match name.as_str() {
+                "h1" | "h2" | "h3" => {
+                    end_block(&mut out);
+                    heading = Some(out.len());
+                }
+                "p" | "ul" => end_block(&mut out),
+                "li" => {
+                    end_line(&mut out);
+                    out.push_str("* ");
+                }
+                "pre" => {
+                    end_block(&mut out);
+                    out.push_str("```\n");
+                    in_pre = true;
+                }
+                "code" if !in_pre => {
+                    out.push('`');
+                    in_code = true;
+                }
+                "em" if !in_pre && !in_code => out.push('*'),
+                "a" => {
+                    out.push('[');
+                    links.push(attribute(attributes, "href").unwrap_or_default());
+                }
+                _ => {}
+            },
+            Token::Close(name) => match name.as_str() {
+                "h1" | "h2" | "h3" => {
+                    if let Some(start) = heading.take() {
+                        // Puzzle titles look like `--- Day 1: Title ---`
+                        let title = out[start..].trim_matches(['-', ' ']).to_owned();
+                        out.truncate(start);
+                        out.push_str("## ");
+                        out.push_str(&title);
+                    }
+
+                    end_block(&mut out);
+                }
+                "p" | "ul" => end_block(&mut out),
+                "li" => end_line(&mut out),
+                "pre" => {
+                    end_line(&mut out);
+                    out.push_str("```");
+                    end_block(&mut out);
+                    in_pre = false;
+                }
+                "code" if !in_pre => {
+                    out.push('`');
+                    in_code = false;
+                }
+                "em" if !in_pre && !in_code => out.push('*'),
+                "a" => {
+                    let href = links.pop().unwrap_or_default();
+                    write!(out, "]({href})").unwrap();
+                }
+                _ => {}
+            },
+            Token::Text(text) if in_pre => out.push_str(&decode_entities(text)),
+            Token::Text(text) => {
+                let text = decode_entities(text);
+                let mut words = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+                if text.starts_with(char::is_whitespace) {
+                    words.insert(0, ' ');
+                }
+
+                if text.ends_with(char::is_whitespace) && !words.ends_with(' ') {
+                    words.push(' ');
+                }
+
+                if out.is_empty() || out.ends_with('\n') {
+                    out.push_str(words.trim_start());
+                } else {
+                    out.push_str(&words);
+                }
+            }
+        }
+    }
+
+    let mut markdown = out.trim().to_owned();
+    markdown.push('\n');
+    markdown
+}
+
+/// Ends the current line, if there is one
+fn end_line(out: &mut String) {
+    out.truncate(out.trim_end_matches(' ').len());
+
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Leaves a blank line after the current block, if there is one
+fn end_block(out: &mut String) {
+    end_line(out);
+
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! markdown_test {
+        ($suite:ident, $($name:ident: $html:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(to_markdown($html), $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    markdown_test!(markdown_tests,
+        heading: "<h2>--- Day 1: Trebuchet?! ---</h2>" => "## Day 1: Trebuchet?!\n",
+        paragraphs: "<p>One\n<em>two</em>.</p><p>Three</p>" => "One *two*.\n\nThree\n",
+        inline_code: "<p>Use <code>a &lt; b</code> here</p>" => "Use `a < b` here\n",
+        emphasis_in_code: "<p><code><em>12</em></code></p>" => "`12`\n",
+        link: "<p>See <a href=\"/2023/day/1/input\">input</a>.</p>" => "See [input](/2023/day/1/input).\n",
+        list: "<ul>\n<li>One</li>\n<li>Two <span title=\"x\">three</span></li>\n</ul>" => "* One\n* Two three\n",
+        pre: "<p>For example:</p>\n<pre><code>1 <em>2</em>\n3 &amp; 4\n</code></pre>\n<p>Done</p>"
+            => "For example:\n\n```\n1 2\n3 & 4\n```\n\nDone\n",
+    );
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("&lt;a&gt; &amp;&amp; &#39;b&#x27; &bogus; &"),
+            "<a> && 'b' &bogus; &"
+        );
+    }
+
+    #[test]
+    fn finds_elements_and_code() {
+        let html = "<main><article class=\"day-desc\"><pre><code>a &lt; b\n</code></pre></article>\
+            <p>x</p><article><p>two</p></article></main>";
+
+        assert_eq!(
+            elements(html, "article"),
+            ["<pre><code>a &lt; b\n</code></pre>", "<p>two</p>"]
+        );
+        assert_eq!(code_blocks(html), ["a < b\n"]);
+    }
+}
@@ -0,0 +1,304 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use crate::error::Error;
+
+/// Where requests go unless `--base-url` says otherwise
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Advent of Code asks automated tools to say who they're from
+const USER_AGENT: &str = "github.com/dqsully/advent-of-code aoc_runner";
+
+/// The body and status code of a response
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Makes requests to the Advent of Code site as a logged-in user, through
+/// `curl` like `advent.sh` does
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.trim().to_owned(),
+        }
+    }
+
+    /// A client using the session cookie saved in `session.txt` under `root`
+    ///
+    /// # Errors
+    /// * `Error::NoSession` - if `session.txt` is missing or empty
+    pub fn from_session_file(base_url: &str, root: &Path) -> Result<Client, Error> {
+        let path = root.join("session.txt");
+
+        match fs::read_to_string(&path) {
+            Ok(session) if !session.trim().is_empty() => Ok(Client::new(base_url, &session)),
+            _ => Err(Error::NoSession(path)),
+        }
+    }
+
+    /// Gets `path`, keeping the body in the `cache` file so it's only
+    /// downloaded again when `refresh` is set
+    ///
+    /// # Errors
+    /// * `Error::Http` - if the request fails or doesn't succeed
+    /// * `Error::Io` or `Error::Write` - if the cache can't be read or written
+    pub fn get_cached(&self, path: &str, cache: &Path, refresh: bool) -> Result<String, Error> {
+        if !refresh {
+            match fs::read_to_string(cache) {
+                Ok(body) => return Ok(body),
+                Err(source) if source.kind() != io::ErrorKind::NotFound => {
+                    return Err(Error::Io {
+                        path: cache.to_owned(),
+                        source,
+                    });
+                }
+                Err(_) => {}
+            }
+        }
+
+        let response = self.request(path, &[])?;
+
+        if !response.is_success() {
+            return Err(Error::Http {
+                url: self.url(path),
+                message: format!("status {}", response.status),
+            });
+        }
+
+        let write_error = |source| Error::Write {
+            path: cache.to_owned(),
+            source,
+        };
+
+        if let Some(dir) = cache.parent() {
+            fs::create_dir_all(dir).map_err(write_error)?;
+        }
+
+        fs::write(cache, &response.body).map_err(write_error)?;
+
+        Ok(response.body)
+    }
+
+    /// Posts `fields` as a URL-encoded form
+    ///
+    /// # Errors
+    /// * `Error::Http` - if the request couldn't be made
+    pub fn post_form(&self, path: &str, fields: &[(&str, &str)]) -> Result<Response, Error> {
+        self.request(path, fields)
+    }
+
+    /// The full URL of `path` on the site
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    fn request(&self, path: &str, fields: &[(&str, &str)]) -> Result<Response, Error> {
+        let url = self.url(path);
+        let http_error = |message: String| Error::Http {
+            url: url.clone(),
+            message,
+        };
+
+        let mut command = Command::new("curl");
+        command
+            // The cookie comes in on stdin, so it doesn't show up in `ps`
+            .args(["--silent", "--show-error", "--header", "@-"])
+            .args(["--user-agent", USER_AGENT])
+            .args(["--write-out", "\n%{http_code}"]);
+
+        for (name, value) in fields {
+            command.args(["--data-urlencode", &format!("{name}={value}")]);
+        }
+
+        let mut child = command
+            .arg(&url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| http_error(format!("couldn't run curl: {error}")))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(stdin, "Cookie: session={}", self.session)
+                .map_err(|error| http_error(format!("couldn't write to curl: {error}")))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|error| http_error(format!("couldn't run curl: {error}")))?;
+
+        if !output.status.success() {
+            return Err(http_error(
+                String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            ));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+
+        Ok(Response {
+            status: status
+                .trim()
+                .parse()
+                .map_err(|_| http_error(format!("no status code in {status:?}")))?,
+            body: body.to_owned(),
+        })
+    }
+}
+
+/// A local server for tests to point a `Client` at
+#[cfg(test)]
+pub mod stub {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serves `responses` in order, one per connection, returning its base URL
+    /// and a handle that joins with every request it got
+    pub fn serve(responses: Vec<(u16, String)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+
+                    request.push_str(&line);
+
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+
+                let mut form = vec![0; content_length];
+                reader.read_exact(&mut form).unwrap();
+                request.push_str(&String::from_utf8(form).unwrap());
+                requests.push(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+
+            requests
+        });
+
+        (base_url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sends_session_and_form() {
+        let (base_url, server) = stub::serve(vec![(200, "<p>ok</p>".to_owned())]);
+
+        let response = Client::new(&base_url, "abc123\n")
+            .post_form("/2023/day/1/answer", &[("level", "1"), ("answer", "a&b")])
+            .unwrap();
+
+        assert_eq!(
+            response,
+            Response {
+                status: 200,
+                body: "<p>ok</p>".to_owned()
+            }
+        );
+
+        let requests = server.join().unwrap();
+        let request = &requests[0];
+
+        assert!(request.starts_with("POST /2023/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc123\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=1&answer=a%26b"));
+    }
+
+    #[test]
+    fn reports_status() {
+        let (base_url, server) = stub::serve(vec![(404, "missing".to_owned())]);
+
+        let response = Client::new(&base_url, "abc")
+            .post_form("/2023/day/30/answer", &[])
+            .unwrap();
+
+        assert_eq!(response.status, 404);
+        assert!(!response.is_success());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn caches_pages() {
+        let cache = std::env::temp_dir().join(format!("aoc_runner_cache_{}", std::process::id()));
+        let _ = fs::remove_file(&cache);
+        let (base_url, server) = stub::serve(vec![
+            (200, "first".to_owned()),
+            (200, "second".to_owned()),
+            (404, "locked".to_owned()),
+        ]);
+        let client = Client::new(&base_url, "abc");
+
+        assert_eq!(client.get_cached("/a", &cache, false).unwrap(), "first");
+        assert_eq!(client.get_cached("/a", &cache, false).unwrap(), "first");
+        assert_eq!(client.get_cached("/a", &cache, true).unwrap(), "second");
+        assert!(matches!(
+            client.get_cached("/b", &cache, true),
+            Err(Error::Http { message, .. }) if message == "status 404"
+        ));
+        assert_eq!(fs::read_to_string(&cache).unwrap(), "second");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /a HTTP/1.1\r\n"));
+
+        fs::remove_file(cache).unwrap();
+    }
+
+    #[test]
+    fn needs_a_session() {
+        let error = Client::from_session_file(DEFAULT_BASE_URL, Path::new("/no/such/repo"))
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.contains("session.txt"));
+    }
+}
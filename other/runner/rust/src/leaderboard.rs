@@ -0,0 +1,239 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use aoc_helpers::leaderboard::Leaderboard;
+
+use crate::{error::Error, http::Client, report};
+
+/// The site asks for private leaderboards to be fetched at most this often
+const REFRESH_INTERVAL: Duration = Duration::from_mins(15);
+
+/// Where a private leaderboard's JSON comes from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Fetch { year: u32, id: u64 },
+}
+
+/// Reads a leaderboard from a file, or fetches it into a cache under `root`
+/// that's reused for 15 minutes unless `refresh` is set
+///
+/// # Errors
+/// * `Error::Io` or `Error::Write` - if the file or cache can't be read or
+///   written
+/// * `Error::NoSession` or `Error::Http` - if the leaderboard can't be fetched
+/// * `Error::Leaderboard` - if the JSON isn't a leaderboard
+pub fn load(
+    source: &Source,
+    root: &Path,
+    base_url: &str,
+    refresh: bool,
+) -> Result<Leaderboard, Error> {
+    let json = match *source {
+        Source::File(ref path) => fs::read_to_string(path).map_err(|source| Error::Io {
+            path: path.clone(),
+            source,
+        })?,
+        Source::Fetch { year, id } => {
+            let cache = root
+                .join(year.to_string())
+                .join(format!("leaderboard_{id}.json"));
+            let stale = fs::metadata(&cache)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .is_none_or(|age| age >= REFRESH_INTERVAL);
+
+            Client::from_session_file(base_url, root)?.get_cached(
+                &format!("/{year}/leaderboard/private/view/{id}.json"),
+                &cache,
+                refresh || stale,
+            )?
+        }
+    };
+
+    Leaderboard::parse(&json).map_err(Error::Leaderboard)
+}
+
+/// Tables of local scores, each day's rankings and each member's times, only
+/// for `day` if there is one
+pub fn report(leaderboard: &Leaderboard, day: Option<u32>) -> String {
+    let days = leaderboard
+        .days()
+        .into_iter()
+        .filter(|&d| day.is_none_or(|day| day == d))
+        .collect::<Vec<_>>();
+
+    let mut sections = vec![format!("Local scores\n{}", scores_table(leaderboard))];
+
+    for &day in &days {
+        sections.push(format!("Day {day}\n{}", day_table(leaderboard, day)));
+    }
+
+    sections.push(format!(
+        "Star timelines\n{}",
+        timelines_table(leaderboard, &days)
+    ));
+
+    sections.join("\n")
+}
+
+fn scores_table(leaderboard: &Leaderboard) -> String {
+    let mut members = leaderboard
+        .members
+        .iter()
+        .zip(leaderboard.local_scores())
+        .collect::<Vec<_>>();
+
+    members.sort_by_key(|(member, score)| (std::cmp::Reverse(*score), member.id));
+
+    let rows = (1..)
+        .zip(members)
+        .map(|(rank, (member, score))| {
+            vec![
+                rank.to_string(),
+                member.display_name(),
+                member.stars.len().to_string(),
+                score.to_string(),
+                member.local_score.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    report::table(&["Rank", "Member", "Stars", "Score", "Site score"], &rows)
+}
+
+fn day_table(leaderboard: &Leaderboard, day: u32) -> String {
+    let unlock = leaderboard.unlock_timestamp(day);
+
+    let rows = (1..)
+        .zip(leaderboard.day_ranking(day))
+        .map(|(rank, day_rank)| {
+            let member = day_rank.member;
+            let [part_1, part_2] = [1, 2].map(|part| {
+                member.star(day, part).map_or_else(
+                    || "-".to_owned(),
+                    |star| report::clock(star.timestamp.saturating_sub(unlock)),
+                )
+            });
+
+            vec![
+                rank.to_string(),
+                member.display_name(),
+                part_1,
+                part_2,
+                member
+                    .delta(day)
+                    .map_or_else(|| "-".to_owned(), report::clock),
+                day_rank.points.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    report::table(
+        &["Rank", "Member", "Part 1", "Part 2", "Delta", "Points"],
+        &rows,
+    )
+}
+
+/// Each member's stars in the order they earned them, with how long after
+/// the puzzle unlocked and how many stars they had by then
+fn timelines_table(leaderboard: &Leaderboard, days: &[u32]) -> String {
+    let mut rows = Vec::new();
+
+    for member in &leaderboard.members {
+        let stars = member.stars.iter().filter(|star| days.contains(&star.day));
+
+        for (count, star) in (1..).zip(stars) {
+            rows.push(vec![
+                member.display_name(),
+                count.to_string(),
+                star.day.to_string(),
+                star.part.to_string(),
+                report::clock(
+                    star.timestamp
+                        .saturating_sub(leaderboard.unlock_timestamp(star.day)),
+                ),
+            ]);
+        }
+    }
+
+    report::table(&["Member", "Star", "Day", "Part", "Time"], &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEADERBOARD: &str = r#"{"event":"2023","owner_id":1,"members":{
+        "1":{"id":1,"name":"Ada","local_score":7,"completion_day_level":{
+            "1":{"1":{"get_star_ts":1701407100,"star_index":1},"2":{"get_star_ts":1701410700,"star_index":3}},
+            "2":{"1":{"get_star_ts":1701493800,"star_index":5}}}},
+        "2":{"id":2,"name":null,"local_score":4,"completion_day_level":{
+            "1":{"1":{"get_star_ts":1701407400,"star_index":2}}}}
+    }}"#;
+
+    #[test]
+    fn prints_tables() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+
+        assert_eq!(
+            report(&leaderboard, None),
+            [
+                "Local scores",
+                "Rank  Member               Stars  Score  Site score",
+                "1     Ada                  3      6      7",
+                "2     (anonymous user #2)  1      1      4",
+                "",
+                "Day 1",
+                "Rank  Member               Part 1   Part 2   Delta    Points",
+                "1     Ada                  0:05:00  1:05:00  1:00:00  4",
+                "2     (anonymous user #2)  0:10:00  -        -        1",
+                "",
+                "Day 2",
+                "Rank  Member  Part 1   Part 2  Delta  Points",
+                "1     Ada     0:10:00  -       -      2",
+                "",
+                "Star timelines",
+                "Member               Star  Day  Part  Time",
+                "Ada                  1     1    1     0:05:00",
+                "Ada                  2     1    2     1:05:00",
+                "Ada                  3     2    1     0:10:00",
+                "(anonymous user #2)  1     1    1     0:10:00",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn filters_to_one_day() {
+        let leaderboard = Leaderboard::parse(LEADERBOARD).unwrap();
+        let report = report(&leaderboard, Some(2));
+
+        assert!(!report.contains("Day 1"));
+        assert!(report.contains("Day 2"));
+        assert!(report.ends_with("Ada     1     2    1     0:10:00\n"));
+    }
+
+    #[test]
+    fn reads_files() {
+        let path =
+            std::env::temp_dir().join(format!("aoc_runner_board_{}.json", std::process::id()));
+        fs::write(&path, LEADERBOARD).unwrap();
+
+        let leaderboard = load(&Source::File(path.clone()), Path::new("."), "", false).unwrap();
+        assert_eq!(leaderboard.members.len(), 2);
+
+        fs::write(&path, "{}").unwrap();
+        assert!(matches!(
+            load(&Source::File(path.clone()), Path::new("."), "", false),
+            Err(Error::Leaderboard(_))
+        ));
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,351 @@
+#![deny(clippy::all, clippy::pedantic)]
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::Path,
+    process::ExitCode,
+    time::{Duration, SystemTime},
+};
+
+use aoc_helpers::answer::Format;
+
+use args::{Args, Command, USAGE};
+use compare::Verdict;
+use error::Error;
+use report::{PartResult, Status};
+use solution::{Language, Program, Solution};
+
+mod answers;
+mod args;
+mod compare;
+mod error;
+mod html;
+mod http;
+mod leaderboard;
+mod pool;
+mod process;
+mod progress;
+mod puzzle;
+mod report;
+mod solution;
+mod submit;
+mod wait;
+
+const PARTS: [u8; 2] = [1, 2];
+
+/// A solution, and either how to run it or why it didn't build
+type Built = (Solution, Result<Program, String>);
+
+fn main() -> ExitCode {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the command, returning whether everything it ran succeeded
+fn run(args: &Args) -> Result<bool, Error> {
+    match &args.command {
+        Command::Help => {
+            println!("{USAGE}");
+            Ok(true)
+        }
+        &Command::Run { year, day } => {
+            let results = run_solutions(&args.root, year, day, args.timeout, args.jobs)?;
+
+            if args.format == Format::Text {
+                println!("{}", report::summary_table(&results));
+            } else {
+                print_records(&results, args.format);
+            }
+
+            Ok(results.iter().all(|result| result.status.is_solved()))
+        }
+        &Command::Compare { year, day } => {
+            let results = compare_solutions(&args.root, year, day, args.timeout, args.jobs)?;
+
+            if args.format != Format::Text {
+                print_records(&results, args.format);
+            }
+
+            let comparisons = compare::compare(results);
+
+            if args.format == Format::Text {
+                println!("{}", compare::comparison_table(&comparisons));
+            }
+
+            Ok(comparisons
+                .iter()
+                .all(|comparison| comparison.verdict() == Verdict::Agree))
+        }
+        Command::Progress { times } => {
+            let mut days = progress::scan(&args.root)?;
+
+            if days.is_empty() {
+                return Err(Error::NoSolutions);
+            }
+
+            if let Some(path) = times {
+                let tsv = fs::read_to_string(path).map_err(|source| Error::Io {
+                    path: path.clone(),
+                    source,
+                })?;
+
+                progress::add_times(&mut days, &progress::parse_times(&tsv));
+            } else {
+                let times = progress::readme_times(&args.root, &days)?;
+                progress::add_times(&mut days, &times);
+            }
+
+            progress::write_readmes(&args.root, &days)?;
+            print!("{}", progress::missing_report(&days));
+
+            Ok(true)
+        }
+        &Command::Puzzle { year, day, refresh } => {
+            let client = http::Client::from_session_file(&args.base_url, &args.root)?;
+            let downloaded = puzzle::download(&client, &args.root, year, day, refresh)?;
+
+            println!("Saved {}", downloaded.markdown.display());
+
+            for example in downloaded.examples {
+                println!("Saved {}", example.display());
+            }
+
+            Ok(true)
+        }
+        &Command::Wait { year, day } => {
+            prepare_day(args, year, day)?;
+            Ok(true)
+        }
+        Command::Leaderboard {
+            source,
+            day,
+            refresh,
+        } => {
+            let leaderboard = leaderboard::load(source, &args.root, &args.base_url, *refresh)?;
+
+            print!("{}", leaderboard::report(&leaderboard, *day));
+
+            Ok(true)
+        }
+        &Command::Submit {
+            year,
+            day,
+            part,
+            ref answer,
+        } => {
+            let client = http::Client::from_session_file(&args.base_url, &args.root)?;
+            let answer = match answer {
+                Some(answer) => answer.clone(),
+                None => solve_part(&args.root, year, day, part, args.timeout)?,
+            };
+
+            println!("Submitting {answer} for {year} day {day} part {part}...");
+
+            let verdict = submit::submit(
+                &client,
+                &args.root,
+                year,
+                day,
+                part,
+                &answer,
+                SystemTime::now(),
+            )?;
+
+            println!("{verdict}");
+
+            Ok(verdict == submit::Verdict::Correct)
+        }
+    }
+}
+
+fn print_records(results: &[PartResult], format: Format) {
+    for result in results {
+        println!("{}", result.record().line(format));
+    }
+}
+
+/// Waits for a day to unlock, then sets up its Rust solution and input
+fn prepare_day(args: &Args, year: u32, day: u32) -> Result<(), Error> {
+    // Check for a session before waiting, not after
+    let client = http::Client::from_session_file(&args.base_url, &args.root)?;
+    let mut clock = wait::SystemClock;
+
+    if wait::count_down(&mut clock, wait::unlock_time(year, day), |seconds| {
+        print!("\r{year} day {day} unlocks in {}", report::clock(seconds));
+        let _ = io::stdout().flush();
+    }) {
+        println!();
+    }
+
+    if let Some(dir) = wait::scaffold(&args.root, year, day)? {
+        println!("Created {}", dir.display());
+    }
+
+    let input = wait::fetch_input(
+        &client,
+        &args.root,
+        year,
+        day,
+        &wait::Backoff::default(),
+        &mut clock,
+    )?;
+    println!("Saved {}", input.display());
+
+    Ok(())
+}
+
+/// Builds every matching solution on the pool
+fn build_solutions(
+    root: &Path,
+    year: Option<u32>,
+    day: Option<u32>,
+    jobs: usize,
+) -> Result<Vec<Built>, Error> {
+    let solutions = solution::discover(root, year, day)?;
+
+    if solutions.is_empty() {
+        return Err(Error::NoSolutions);
+    }
+
+    eprintln!("Building {} solutions...", solutions.len());
+
+    Ok(pool::map(solutions, jobs, |solution| {
+        let program = solution.build();
+        (solution, program)
+    }))
+}
+
+/// Builds every matching solution, then runs each of their parts on the pool
+fn run_solutions(
+    root: &Path,
+    year: Option<u32>,
+    day: Option<u32>,
+    timeout: Duration,
+    jobs: usize,
+) -> Result<Vec<PartResult>, Error> {
+    let built = build_solutions(root, year, day, jobs)?;
+
+    let part_jobs = built
+        .iter()
+        .flat_map(|(solution, program)| PARTS.map(|part| (solution, program, part)))
+        .collect();
+
+    eprintln!("Running parts...");
+
+    Ok(pool::map(part_jobs, jobs, |(solution, program, part)| {
+        run_part(solution, program, part, timeout)
+    }))
+}
+
+/// Builds every matching solution, then runs each of them once on the pool,
+/// since not every language can run a single part
+fn compare_solutions(
+    root: &Path,
+    year: Option<u32>,
+    day: Option<u32>,
+    timeout: Duration,
+    jobs: usize,
+) -> Result<Vec<PartResult>, Error> {
+    let built = build_solutions(root, year, day, jobs)?;
+
+    eprintln!("Running solutions...");
+
+    let results = pool::map(built, jobs, |(solution, program)| {
+        let output = program.map(|program| {
+            process::run_with_timeout(&mut program.command(), timeout)
+                .map_err(|error| format!("couldn't run: {error}"))
+        });
+
+        PARTS.map(|part| {
+            let (status, duration, parse_duration) = match &output {
+                Err(error) => (Status::BuildFailed(error.clone()), None, None),
+                Ok(Err(error)) => (Status::Failed(error.clone()), None, None),
+                Ok(Ok(output)) => (
+                    report::part_status(part, output),
+                    Some(output.duration),
+                    report::parse_time(output),
+                ),
+            };
+
+            PartResult {
+                year: solution.year,
+                day: solution.day,
+                language: solution.language,
+                part,
+                status,
+                duration,
+                parse_duration,
+            }
+        })
+    });
+
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// Builds and runs the Rust solution to one part, for its answer
+fn solve_part(
+    root: &Path,
+    year: u32,
+    day: u32,
+    part: u8,
+    timeout: Duration,
+) -> Result<String, Error> {
+    let solution = solution::discover(root, Some(year), Some(day))?
+        .into_iter()
+        .find(|solution| solution.language == Language::Rust)
+        .ok_or(Error::NoSolutions)?;
+
+    eprintln!("Building {year} day {day}...");
+
+    match run_part(&solution, &solution.build(), part, timeout).status {
+        report::Status::Solved(answer) => Ok(answer),
+        status => Err(Error::NoAnswer(
+            format!("{} {}", status.label(), status.details())
+                .trim()
+                .to_owned(),
+        )),
+    }
+}
+
+fn run_part(
+    solution: &Solution,
+    program: &Result<Program, String>,
+    part: u8,
+    timeout: Duration,
+) -> PartResult {
+    let (status, duration, parse_duration) = match program {
+        Err(error) => (Status::BuildFailed(error.clone()), None, None),
+        Ok(program) => match process::run_with_timeout(&mut program.part_command(part), timeout) {
+            Ok(output) => (
+                report::part_status(part, &output),
+                Some(output.duration),
+                report::parse_time(&output),
+            ),
+            Err(error) => (Status::Failed(format!("couldn't run: {error}")), None, None),
+        },
+    };
+
+    PartResult {
+        year: solution.year,
+        day: solution.day,
+        language: solution.language,
+        part,
+        status,
+        duration,
+        parse_duration,
+    }
+}
@@ -0,0 +1,76 @@
+use std::{num::NonZeroUsize, sync::Mutex, thread};
+
+/// The number of threads to use when none is given, one per core
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Calls `f` on every job using up to `threads` threads, returning the results
+/// in the same order as the jobs.
+///
+/// Each thread takes the next job as soon as it's done with its last one, so a
+/// slow job doesn't hold up the others.
+pub fn map<T, R, F>(jobs: Vec<T>, threads: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let len = jobs.len();
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let results = Mutex::new((0..len).map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, len.max(1)) {
+            scope.spawn(|| loop {
+                // Release the queue before running the job
+                let Some((i, job)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+
+                let result = f(job);
+                results.lock().unwrap()[i] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every job runs once"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    #[test]
+    fn keeps_job_order() {
+        let results = map((0..20).collect(), 4, |n: u64| {
+            thread::sleep(Duration::from_millis(20 - n));
+            n * n
+        });
+
+        assert_eq!(results, (0..20).map(|n| n * n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn runs_in_parallel() {
+        let start = Instant::now();
+
+        map(vec![(); 4], 4, |()| {
+            thread::sleep(Duration::from_millis(100));
+        });
+
+        assert!(start.elapsed() < Duration::from_millis(350));
+    }
+
+    #[test]
+    fn no_jobs() {
+        assert!(map(Vec::<()>::new(), 0, |()| ()).is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    io::{self, Read},
+    os::unix::process::CommandExt,
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// What a finished (or killed) process printed
+#[derive(Debug)]
+pub struct Output {
+    /// `None` if the process was killed for running past its timeout
+    pub status: Option<ExitStatus>,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration: Duration,
+}
+
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Runs `command` to completion, or kills it once `timeout` has passed. It
+/// runs in a process group of its own, so anything it starts (like the
+/// program `go run` builds) is killed with it.
+pub fn run_with_timeout(command: &mut Command, timeout: Duration) -> io::Result<Output> {
+    let start = Instant::now();
+    let mut child = command
+        .process_group(0)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Drain the pipes as the process runs, so it can't block on a full one
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let status = wait_until(&mut child, start + timeout)?;
+    let duration = start.elapsed();
+
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+        duration,
+    })
+}
+
+fn wait_until(child: &mut Child, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+
+        if Instant::now() >= deadline {
+            // Grandchildren would otherwise keep the pipes open after it dies
+            Command::new("kill")
+                .args(["-KILL", "--", &format!("-{}", child.id())])
+                .stderr(Stdio::null())
+                .status()?;
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn read_in_background<R>(pipe: Option<R>) -> thread::JoinHandle<String>
+where
+    R: Read + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut out = String::new();
+
+        if let Some(mut pipe) = pipe {
+            // Whatever was read before an error is still worth reporting
+            let _ = pipe.read_to_string(&mut out);
+        }
+
+        out
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sh(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    #[test]
+    fn captures_output() {
+        let output = run_with_timeout(
+            &mut sh("echo 'Part 1: 42'; echo oops >&2; exit 3"),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+
+        assert_eq!(output.status.and_then(|status| status.code()), Some(3));
+        assert_eq!(output.stdout, "Part 1: 42\n");
+        assert_eq!(output.stderr, "oops\n");
+    }
+
+    #[test]
+    fn kills_after_timeout() {
+        let output = run_with_timeout(
+            &mut sh("echo started; exec sleep 10"),
+            Duration::from_millis(100),
+        )
+        .unwrap();
+
+        assert!(output.status.is_none());
+        assert_eq!(output.stdout, "started\n");
+        assert!(output.duration < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn kills_grandchildren_after_timeout() {
+        let start = Instant::now();
+        let output = run_with_timeout(
+            &mut sh("echo started; sleep 10; :"),
+            Duration::from_millis(100),
+        )
+        .unwrap();
+
+        assert!(output.status.is_none());
+        assert_eq!(output.stdout, "started\n");
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
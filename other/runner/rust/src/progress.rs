@@ -0,0 +1,480 @@
+use std::{fmt::Write, fs, path::Path, time::Duration};
+
+use aoc_helpers::hash::FastHashMap;
+
+use crate::{answers, error::Error, report, solution::Language};
+
+/// Where the generated part of the top-level README goes
+pub const START_MARKER: &str = "<!-- progress:start -->";
+pub const END_MARKER: &str = "<!-- progress:end -->";
+
+/// Everything the progress tables show about a day
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayProgress {
+    pub year: u32,
+    pub day: u32,
+    pub languages: Vec<Language>,
+    /// The recorded answer to each part, which earns its star
+    pub answers: [Option<String>; 2],
+    /// The fastest time for each part across languages
+    pub times: [Option<Duration>; 2],
+    pub examples: usize,
+    /// The languages with a write-up in their own directory's README
+    pub write_ups: Vec<Language>,
+}
+
+impl DayProgress {
+    pub fn stars(&self) -> usize {
+        self.answers.iter().flatten().count()
+    }
+}
+
+/// Finds every day with a solution under `root`, with its answers, examples
+/// and write-ups
+///
+/// # Errors
+/// * `Error::Io` - if a directory or answers file can't be read
+pub fn scan(root: &Path) -> Result<Vec<DayProgress>, Error> {
+    let mut days: Vec<DayProgress> = Vec::new();
+
+    for solution in crate::solution::discover(root, None, None)? {
+        if let Some(day) = days
+            .last_mut()
+            .filter(|day| (day.year, day.day) == (solution.year, solution.day))
+        {
+            day.languages.push(solution.language);
+
+            if solution.dir.join("README.md").is_file() {
+                day.write_ups.push(solution.language);
+            }
+
+            continue;
+        }
+
+        let day_dir = solution.dir.parent().unwrap_or(root);
+        let io_error = |source| Error::Io {
+            path: day_dir.to_owned(),
+            source,
+        };
+
+        let mut examples = 0;
+
+        for entry in fs::read_dir(day_dir).map_err(io_error)? {
+            let name = entry.map_err(io_error)?.file_name();
+            let name = name.to_string_lossy();
+
+            if name.starts_with("example_") && name.ends_with(".txt") {
+                examples += 1;
+            }
+        }
+
+        days.push(DayProgress {
+            year: solution.year,
+            day: solution.day,
+            languages: vec![solution.language],
+            answers: answers::read(day_dir)?,
+            times: [None, None],
+            examples,
+            write_ups: if solution.dir.join("README.md").is_file() {
+                vec![solution.language]
+            } else {
+                Vec::new()
+            },
+        });
+    }
+
+    Ok(days)
+}
+
+/// The fastest time for each year, day and part in the output of
+/// `run --format tsv`, skipping parts that didn't solve
+pub fn parse_times(tsv: &str) -> FastHashMap<(u32, u32, u8), Duration> {
+    let mut times = FastHashMap::default();
+
+    for line in tsv.lines() {
+        let fields = line.split('\t').collect::<Vec<_>>();
+
+        // Later columns, like `parse_duration`, don't matter here
+        let [year, day, part, answer, _error, duration, _language, ..] = fields[..] else {
+            continue;
+        };
+
+        let (Ok(year), Ok(day), Ok(part), Ok(seconds)) = (
+            year.parse(),
+            day.parse(),
+            part.parse(),
+            duration.parse::<f64>(),
+        ) else {
+            continue;
+        };
+
+        let Ok(duration) = Duration::try_from_secs_f64(seconds) else {
+            continue;
+        };
+
+        if answer.is_empty() {
+            continue;
+        }
+
+        times
+            .entry((year, day, part))
+            .and_modify(|fastest: &mut Duration| *fastest = (*fastest).min(duration))
+            .or_insert(duration);
+    }
+
+    times
+}
+
+/// Fills in each day's times from `parse_times`
+pub fn add_times(days: &mut [DayProgress], times: &FastHashMap<(u32, u32, u8), Duration>) {
+    for day in days {
+        day.times = [1, 2].map(|part| times.get(&(day.year, day.day, part)).copied());
+    }
+}
+
+/// The times already in each year's README under `root`, so regenerating
+/// the tables without new times keeps them. Years without a README have none.
+///
+/// # Errors
+/// * `Error::Io` - if a README exists but can't be read
+pub fn readme_times(
+    root: &Path,
+    days: &[DayProgress],
+) -> Result<FastHashMap<(u32, u32, u8), Duration>, Error> {
+    let mut times = FastHashMap::default();
+
+    for year in years(days) {
+        let path = root.join(year.to_string()).join("README.md");
+
+        if !path.is_file() {
+            continue;
+        }
+
+        let readme = fs::read_to_string(&path).map_err(|source| Error::Io {
+            path: path.clone(),
+            source,
+        })?;
+
+        times.extend(parse_readme_times(year, &readme));
+    }
+
+    Ok(times)
+}
+
+/// The part times in the rows of a table written by `year_readme`
+pub fn parse_readme_times(year: u32, readme: &str) -> FastHashMap<(u32, u32, u8), Duration> {
+    let mut times = FastHashMap::default();
+
+    for line in readme.lines() {
+        let cells = line.split('|').map(str::trim).collect::<Vec<_>>();
+
+        let ["", day, _stars, _languages, part_1, part_2, _write_up, ""] = cells[..] else {
+            continue;
+        };
+
+        // The day column is a link, like `[01](./01)`
+        let Some(Ok(day)) = day
+            .strip_prefix('[')
+            .and_then(|day| day.split_once(']'))
+            .map(|(day, _)| day.parse())
+        else {
+            continue;
+        };
+
+        for (part, time) in [(1, part_1), (2, part_2)] {
+            if let Some(time) = report::parse_duration(time) {
+                times.insert((year, day, part), time);
+            }
+        }
+    }
+
+    times
+}
+
+/// The years list for the top-level README, like
+/// `* [2023](./2023) (13/25 Rust, 26/50 stars)`
+pub fn years_list(days: &[DayProgress]) -> String {
+    let mut list = String::new();
+
+    for year in years(days) {
+        let year_days = days.iter().filter(|day| day.year == year);
+        let mut counts = Vec::new();
+
+        for language in Language::ALL {
+            let count = year_days
+                .clone()
+                .filter(|day| day.languages.contains(&language))
+                .count();
+
+            if count > 0 {
+                counts.push(format!("{count}/25 {language}"));
+            }
+        }
+
+        // Before any answers are recorded, stars would only look like no
+        // progress
+        let stars = year_days.map(DayProgress::stars).sum::<usize>();
+
+        if stars > 0 {
+            counts.push(format!("{stars}/50 stars"));
+        }
+
+        writeln!(list, "* [{year}](./{year}) ({})", counts.join(", ")).unwrap();
+    }
+
+    list
+}
+
+/// The README for one year, with a row per day it has solutions for
+pub fn year_readme(year: u32, days: &[DayProgress]) -> String {
+    let mut readme = format!(
+        "# {year}\n\n\
+        <!-- Generated by `./advent.sh runner progress`, so edits here will be overwritten -->\n\n\
+        | Day | Stars | Languages | Part 1 | Part 2 | Write-up |\n\
+        | --- | --- | --- | --- | --- | --- |\n"
+    );
+
+    for day in days.iter().filter(|day| day.year == year) {
+        let dir = format!("./{:02}", day.day);
+        let languages = day
+            .languages
+            .iter()
+            .map(|language| format!("[{language}]({dir}/{})", language.dir_name()))
+            .collect::<Vec<_>>();
+        let [part_1, part_2] = day
+            .times
+            .map(|time| time.map_or_else(|| "-".to_owned(), |time| format!("{time:.2?}")));
+        let stars = match day.stars() {
+            0 => "-".to_owned(),
+            stars => "⭐".repeat(stars),
+        };
+        let write_up = if day.write_ups.is_empty() {
+            "-".to_owned()
+        } else {
+            day.write_ups
+                .iter()
+                .map(|language| format!("[{language}]({dir}/{}/README.md)", language.dir_name()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        writeln!(
+            readme,
+            "| [{:02}]({dir}) | {stars} | {} | {part_1} | {part_2} | {write_up} |",
+            day.day,
+            languages.join(", "),
+        )
+        .unwrap();
+    }
+
+    readme
+}
+
+/// Swaps whatever is between the progress markers in `readme` for
+/// `generated`, or `None` if the markers are missing
+pub fn replace_generated(readme: &str, generated: &str) -> Option<String> {
+    let start = readme.find(START_MARKER)? + START_MARKER.len();
+    let end = start + readme[start..].find(END_MARKER)?;
+
+    Some(format!(
+        "{}\n{generated}{}",
+        &readme[..start],
+        &readme[end..]
+    ))
+}
+
+/// A line for each day that's missing examples or a write-up
+pub fn missing_report(days: &[DayProgress]) -> String {
+    let mut report = String::new();
+
+    for day in days {
+        let missing = [
+            (day.examples == 0, "examples"),
+            (day.write_ups.is_empty(), "write-up"),
+        ]
+        .into_iter()
+        .filter_map(|(missing, name)| missing.then_some(name))
+        .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            writeln!(
+                report,
+                "{}/{:02} is missing {}",
+                day.year,
+                day.day,
+                missing.join(" and ")
+            )
+            .unwrap();
+        }
+    }
+
+    report
+}
+
+/// Regenerates each year's README and the years list in the top-level one
+///
+/// # Errors
+/// * `Error::Io` - if the top-level README can't be read
+/// * `Error::Write` - if a README can't be written
+/// * `Error::MissingMarkers` - if the top-level README has nowhere to put the
+///   years list
+pub fn write_readmes(root: &Path, days: &[DayProgress]) -> Result<(), Error> {
+    let write = |path: &Path, contents: &str| {
+        fs::write(path, contents).map_err(|source| Error::Write {
+            path: path.to_owned(),
+            source,
+        })
+    };
+
+    for year in years(days) {
+        write(
+            &root.join(year.to_string()).join("README.md"),
+            &year_readme(year, days),
+        )?;
+    }
+
+    let path = root.join("README.md");
+    let readme = fs::read_to_string(&path).map_err(|source| Error::Io {
+        path: path.clone(),
+        source,
+    })?;
+    let readme = replace_generated(&readme, &years_list(days))
+        .ok_or_else(|| Error::MissingMarkers(path.clone()))?;
+
+    write(&path, &readme)
+}
+
+/// Every year with a solution, in order
+pub fn years(days: &[DayProgress]) -> Vec<u32> {
+    let mut years = days.iter().map(|day| day.year).collect::<Vec<_>>();
+    years.dedup();
+    years
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: u32, languages: Vec<Language>, answers: usize, write_up: bool) -> DayProgress {
+        DayProgress {
+            year: 2023,
+            day,
+            languages,
+            answers: [answers >= 1, answers >= 2].map(|solved| solved.then(|| "1".to_owned())),
+            times: [None, None],
+            examples: usize::from(write_up),
+            write_ups: if write_up {
+                vec![Language::Rust]
+            } else {
+                Vec::new()
+            },
+        }
+    }
+
+    #[test]
+    fn tables() {
+        let mut days = vec![
+            day(1, vec![Language::Rust, Language::Go], 2, true),
+            day(2, vec![Language::Rust], 1, false),
+        ];
+
+        let mut times = FastHashMap::default();
+        times.insert((2023, 1, 1), Duration::from_micros(1500));
+        add_times(&mut days, &times);
+
+        assert_eq!(
+            years_list(&days),
+            "* [2023](./2023) (2/25 Rust, 1/25 Go, 3/50 stars)\n"
+        );
+        assert_eq!(
+            years_list(&[day(3, vec![Language::Rust], 0, false)]),
+            "* [2023](./2023) (1/25 Rust)\n"
+        );
+        assert_eq!(
+            year_readme(2023, &days),
+            [
+                "# 2023",
+                "",
+                "<!-- Generated by `./advent.sh runner progress`, so edits here will be overwritten -->",
+                "",
+                "| Day | Stars | Languages | Part 1 | Part 2 | Write-up |",
+                "| --- | --- | --- | --- | --- | --- |",
+                "| [01](./01) | ⭐⭐ | [Rust](./01/rust), [Go](./01/go) | 1.50ms | - | [Rust](./01/rust/README.md) |",
+                "| [02](./02) | ⭐ | [Rust](./02/rust) | - | - | - |",
+                "",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            missing_report(&days),
+            "2023/02 is missing examples and write-up\n"
+        );
+    }
+
+    #[test]
+    fn finds_language_write_ups() {
+        let root = std::env::temp_dir().join(format!("aoc_runner_progress_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        for dir in ["2023/01/rust", "2023/01/go", "2023/02/rust"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+
+        // A day's own README only links its languages
+        fs::write(root.join("2023/01/README.md"), "* [Rust](./rust)\n").unwrap();
+        fs::write(root.join("2023/01/go/README.md"), "# 2023 Day 1 - Go\n").unwrap();
+        fs::write(root.join("2023/01/example_1.txt"), "1abc2\n").unwrap();
+
+        let days = scan(&root).unwrap();
+
+        assert_eq!(days[0].write_ups, [Language::Go]);
+        assert!(days[1].write_ups.is_empty());
+        assert!(year_readme(2023, &days).contains("| [Go](./01/go/README.md) |"));
+        assert_eq!(
+            missing_report(&days),
+            "2023/02 is missing examples and write-up\n"
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn times_keep_the_fastest_solved_part() {
+        let times = parse_times(
+            "2023\t1\t1\t54239\t\t0.002\trust\n\
+            2023\t1\t1\t54239\t\t0.001\tgo\n\
+            2023\t1\t2\t\ttimeout\t10\trust\n\
+            not a record\n",
+        );
+
+        assert_eq!(times.get(&(2023, 1, 1)), Some(&Duration::from_millis(1)));
+        assert_eq!(times.get(&(2023, 1, 2)), None);
+    }
+
+    #[test]
+    fn times_read_back_from_readme() {
+        let mut days = vec![
+            day(1, vec![Language::Rust], 2, true),
+            day(2, vec![Language::Rust], 1, false),
+        ];
+
+        let mut times = FastHashMap::default();
+        times.insert((2023, 1, 1), Duration::from_micros(1500));
+        times.insert((2023, 2, 2), Duration::from_secs(2));
+        add_times(&mut days, &times);
+
+        assert_eq!(parse_readme_times(2023, &year_readme(2023, &days)), times);
+        assert!(parse_readme_times(2023, "| Day | Stars |\n| --- | --- |\n").is_empty());
+    }
+
+    #[test]
+    fn replaces_between_markers() {
+        let readme = format!("Years:\n{START_MARKER}\n* old\n{END_MARKER}\n\nMore");
+
+        assert_eq!(
+            replace_generated(&readme, "* new\n").unwrap(),
+            format!("Years:\n{START_MARKER}\n* new\n{END_MARKER}\n\nMore")
+        );
+        assert_eq!(replace_generated("no markers", "* new\n"), None);
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{error::Error, html, http::Client};
+
+/// Where a day's puzzle page is cached, next to its input
+pub const PAGE_FILE: &str = "puzzle.html";
+
+/// The Markdown version of a day's puzzle description
+pub const MARKDOWN_FILE: &str = "puzzle.md";
+
+/// What downloading a puzzle wrote
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Downloaded {
+    pub markdown: PathBuf,
+    /// The example files written, which skips any that already existed
+    pub examples: Vec<PathBuf>,
+}
+
+/// Fetches a day's puzzle page, or reuses the cached one unless `refresh` is
+/// set (say, once part 2 unlocks). Saves its description as Markdown, and each
+/// code block in it as an `example_N.txt` fixture for `--example N`.
+///
+/// # Errors
+/// * `Error::Http` - if the page can't be fetched
+/// * `Error::NoPuzzle` - if the page has no puzzle description
+/// * `Error::Io` or `Error::Write` - if a file can't be read or written
+pub fn download(
+    client: &Client,
+    root: &Path,
+    year: u32,
+    day: u32,
+    refresh: bool,
+) -> Result<Downloaded, Error> {
+    let day_dir = root.join(year.to_string()).join(format!("{day:02}"));
+    let page = client.get_cached(
+        &format!("/{year}/day/{day}"),
+        &day_dir.join(PAGE_FILE),
+        refresh,
+    )?;
+
+    let articles = html::elements(&page, "article");
+
+    if articles.is_empty() {
+        return Err(Error::NoPuzzle { year, day });
+    }
+
+    let markdown = articles
+        .iter()
+        .map(|article| html::to_markdown(article))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let markdown_path = day_dir.join(MARKDOWN_FILE);
+    write(&markdown_path, &markdown)?;
+
+    let mut examples = Vec::new();
+    let blocks = articles
+        .iter()
+        .flat_map(|article| html::code_blocks(article));
+
+    for (n, block) in (1..).zip(blocks) {
+        let path = day_dir.join(format!("example_{n}.txt"));
+
+        if !path.exists() {
+            write(&path, &block)?;
+            examples.push(path);
+        }
+    }
+
+    Ok(Downloaded {
+        markdown: markdown_path,
+        examples,
+    })
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), Error> {
+    fs::write(path, contents).map_err(|source| Error::Write {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::stub;
+
+    const PAGE: &str = include_str!("../fixtures/puzzle.html");
+
+    #[test]
+    fn converts_saved_page() {
+        let markdown = html::elements(PAGE, "article")
+            .iter()
+            .map(|article| html::to_markdown(article))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            markdown,
+            [
+                "## Day 1: Counting Sheep\n\
+                \n\
+                The sheep have escaped, and you need to count them *before* the farmer notices.\n\
+                \n\
+                Each line of the [input](/2023/day/1/input) lists a pen and how many sheep are in it:\n\
+                \n\
+                ```\n\
+                north 3\n\
+                south 12\n\
+                east <none> & west 5\n\
+                ```\n\
+                \n\
+                In this example:\n\
+                \n\
+                * The `north` pen has `3` sheep.\n\
+                * The `east` pen is empty.\n\
+                \n\
+                What is the *total number of sheep*?\n",
+                "## Part Two\n\
+                \n\
+                The farmer counts pens in pairs:\n\
+                \n\
+                ```\n\
+                north 1\n\
+                south 2\n\
+                ```\n\
+                \n\
+                What do you get if you multiply every `x * y`?\n",
+            ]
+        );
+    }
+
+    #[test]
+    fn saves_description_and_examples() {
+        let root = std::env::temp_dir().join(format!("aoc_runner_puzzle_{}", std::process::id()));
+        let day_dir = root.join("2023/01");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&day_dir).unwrap();
+        fs::write(day_dir.join("example_2.txt"), "mine\n").unwrap();
+
+        let (base_url, server) = stub::serve(vec![(200, PAGE.to_owned())]);
+        let client = Client::new(&base_url, "abc");
+
+        let downloaded = download(&client, &root, 2023, 1, false).unwrap();
+
+        assert_eq!(
+            downloaded,
+            Downloaded {
+                markdown: day_dir.join(MARKDOWN_FILE),
+                examples: vec![day_dir.join("example_1.txt")],
+            }
+        );
+        assert!(fs::read_to_string(&downloaded.markdown)
+            .unwrap()
+            .starts_with("## Day 1: Counting Sheep\n"));
+        assert_eq!(
+            fs::read_to_string(day_dir.join("example_1.txt")).unwrap(),
+            "north 3\nsouth 12\neast <none> & west 5\n"
+        );
+        assert_eq!(
+            fs::read_to_string(day_dir.join("example_2.txt")).unwrap(),
+            "mine\n"
+        );
+
+        // The second download comes from the cache, so the stub isn't needed
+        server.join().unwrap();
+        assert!(download(&client, &root, 2023, 1, false).is_ok());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn locked_page() {
+        let root = std::env::temp_dir().join(format!("aoc_runner_locked_{}", std::process::id()));
+        let (base_url, server) = stub::serve(vec![(200, "<main><p>Not yet</p></main>".to_owned())]);
+
+        assert_eq!(
+            download(&Client::new(&base_url, "abc"), &root, 2023, 25, false)
+                .unwrap_err()
+                .to_string(),
+            "no puzzle description on the page for 2023 day 25"
+        );
+
+        server.join().unwrap();
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,385 @@
+use std::{fmt::Write, time::Duration};
+
+use aoc_helpers::answer::AnswerRecord;
+
+use crate::{process::Output, solution::Language};
+
+/// How running one part of a solution went
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Solved(String),
+    Failed(String),
+    Panicked(String),
+    TimedOut,
+    BuildFailed(String),
+}
+
+impl Status {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Solved(_) => "ok",
+            Status::Failed(_) => "error",
+            Status::Panicked(_) => "panic",
+            Status::TimedOut => "timeout",
+            Status::BuildFailed(_) => "build failed",
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        matches!(self, Status::Solved(_))
+    }
+
+    /// The answer, or what went wrong
+    pub fn details(&self) -> &str {
+        match self {
+            Status::Solved(details)
+            | Status::Failed(details)
+            | Status::Panicked(details)
+            | Status::BuildFailed(details) => details,
+            Status::TimedOut => "",
+        }
+    }
+}
+
+/// The outcome of running one part of one solution
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartResult {
+    pub year: u32,
+    pub day: u32,
+    pub language: Language,
+    pub part: u8,
+    pub status: Status,
+    /// `None` if the part never ran
+    pub duration: Option<Duration>,
+    /// How long parsing the input took, for solutions that parse it once and
+    /// say so
+    pub parse_duration: Option<Duration>,
+}
+
+impl PartResult {
+    /// The result as a record for `--format json` or `tsv`, where anything but
+    /// an answer is an error
+    pub fn record(&self) -> AnswerRecord {
+        let answer = match &self.status {
+            Status::Solved(answer) => Ok(answer.clone()),
+            Status::TimedOut => Err(self.status.label().to_owned()),
+            status => Err(format!("{}: {}", status.label(), status.details())),
+        };
+
+        AnswerRecord {
+            year: Some(self.year),
+            day: Some(self.day),
+            part: self.part,
+            answer,
+            duration: self.duration,
+            language: self.language.dir_name().to_owned(),
+            parse_duration: self.parse_duration,
+        }
+    }
+}
+
+/// Works out how a part went from what its process printed. A part that
+/// printed its answer counts as solved even if the other part then failed.
+pub fn part_status(part: u8, output: &Output) -> Status {
+    let answer_prefix = format!("Part {part}: ");
+    let error_prefix = format!("Part {part} error!: ");
+
+    if let Some(answer) = output
+        .stdout
+        .lines()
+        .find_map(|line| line.strip_prefix(&answer_prefix))
+    {
+        return Status::Solved(answer.trim().to_owned());
+    }
+
+    let Some(exit) = output.status else {
+        return Status::TimedOut;
+    };
+
+    // Rust exits with 101 when the main thread panics, printing the message on
+    // the line after "thread 'main' panicked at <location>:"
+    if exit.code() == Some(101) {
+        let mut lines = output.stderr.lines();
+        let message = lines
+            .find(|line| line.contains("panicked at"))
+            .and_then(|_| lines.next());
+
+        return Status::Panicked(message.map_or_else(|| first_line(&output.stderr), str::to_owned));
+    }
+
+    let message = output
+        .stdout
+        .lines()
+        .find_map(|line| {
+            line.strip_prefix(&error_prefix)
+                .or_else(|| line.strip_prefix("Parse error!: "))
+        })
+        .map_or_else(
+            || first_line(output.stderr.trim_start().trim_start_matches("error: ")),
+            str::to_owned,
+        );
+
+    Status::Failed(message)
+}
+
+/// How long parsing took, from the `Parse time: 1.2ms` line a Rust day
+/// prints with `--time`
+pub fn parse_time(output: &Output) -> Option<Duration> {
+    let time = output
+        .stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Parse time: "))?;
+
+    parse_duration(time)
+}
+
+/// Reads a duration back from `Duration`'s debug format, like `1.2ms`, which
+/// picks the unit to fit
+pub fn parse_duration(time: &str) -> Option<Duration> {
+    let time = time.trim();
+    let (number, scale) = [("ns", 1e-9), ("µs", 1e-6), ("ms", 1e-3), ("s", 1.0)]
+        .into_iter()
+        .find_map(|(unit, scale)| Some((time.strip_suffix(unit)?, scale)))?;
+
+    Duration::try_from_secs_f64(number.parse::<f64>().ok()? * scale).ok()
+}
+
+fn first_line(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("no output")
+        .to_owned()
+}
+
+const MAX_DETAILS: usize = 60;
+
+/// A table of every part's status and duration, followed by a count of each
+/// status
+pub fn summary_table(results: &[PartResult]) -> String {
+    let header = [
+        "Year", "Day", "Language", "Part", "Status", "Time", "Parse", "Answer",
+    ];
+    let time = |duration: Option<Duration>| {
+        duration.map_or_else(|| "-".to_owned(), |duration| format!("{duration:.2?}"))
+    };
+    let rows = results
+        .iter()
+        .map(|result| {
+            [
+                result.year.to_string(),
+                format!("{:02}", result.day),
+                result.language.to_string(),
+                result.part.to_string(),
+                result.status.label().to_owned(),
+                time(result.duration),
+                time(result.parse_duration),
+                truncate(result.status.details(), MAX_DETAILS),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let mut table = table(&header, &rows);
+
+    let solved = results
+        .iter()
+        .filter(|result| result.status.is_solved())
+        .count();
+    write!(table, "\n{solved}/{} parts solved", results.len()).unwrap();
+
+    for label in ["error", "panic", "timeout", "build failed"] {
+        let count = results
+            .iter()
+            .filter(|result| result.status.label() == label)
+            .count();
+
+        if count > 0 {
+            write!(table, ", {count} {label}").unwrap();
+        }
+    }
+
+    table
+}
+
+/// Lines up `rows` in columns under `header`, a line each
+pub fn table<R: AsRef<[String]>>(header: &[&str], rows: &[R]) -> String {
+    let mut widths = header.iter().map(|cell| cell.len()).collect::<Vec<_>>();
+
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row.as_ref()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    let header = header
+        .iter()
+        .map(|&cell| cell.to_owned())
+        .collect::<Vec<_>>();
+
+    for row in std::iter::once(header.as_slice()).chain(rows.iter().map(AsRef::as_ref)) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        writeln!(table, "{}", line.trim_end()).unwrap();
+    }
+
+    table
+}
+
+/// Seconds as `h:mm:ss`
+pub fn clock(seconds: u64) -> String {
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_owned()
+    } else {
+        let mut out = text.chars().take(max - 3).collect::<String>();
+        out.push_str("...");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{os::unix::process::ExitStatusExt, process::ExitStatus};
+
+    use super::*;
+
+    fn output(code: Option<i32>, stdout: &str, stderr: &str) -> Output {
+        Output {
+            // Wait statuses keep the exit code in the second byte
+            status: code.map(|code| ExitStatus::from_raw(code << 8)),
+            stdout: stdout.to_owned(),
+            stderr: stderr.to_owned(),
+            duration: Duration::from_millis(1),
+        }
+    }
+
+    #[test]
+    fn parse_times() {
+        let time = |stdout| parse_time(&output(Some(0), stdout, ""));
+
+        assert_eq!(
+            time("Parse time: 1.5ms\nPart 1: 3\n"),
+            Some(Duration::from_micros(1500))
+        );
+        assert_eq!(time("Parse time: 12µs\n"), Some(Duration::from_micros(12)));
+        assert_eq!(
+            time("Parse time: 2.25s\n"),
+            Some(Duration::from_millis(2250))
+        );
+        assert_eq!(time("Parse time: 80ns\n"), Some(Duration::from_nanos(80)));
+        assert_eq!(time("Part 1: 3\nPart 1 time: 1ms\n"), None);
+    }
+
+    macro_rules! status_test {
+        ($suite:ident, $($name:ident: $output:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(part_status(2, &$output), $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    status_test!(status_tests,
+        solved: output(Some(0), "Part 2: 71503\n", "") => Status::Solved("71503".to_owned()),
+        solved_with_time: output(Some(0), "Part 2: 5\nPart 2 time: 1ms\n", "") => Status::Solved("5".to_owned()),
+        part_error: output(Some(1), "Part 2 error!: no start found\n", "") => Status::Failed("no start found".to_owned()),
+        parse_error: output(Some(1), "Parse error!: bad line 3\n", "") => Status::Failed("bad line 3".to_owned()),
+        missing_input: output(Some(1), "", "error: input file ../input.txt doesn't exist\n")
+            => Status::Failed("input file ../input.txt doesn't exist".to_owned()),
+        panicked: output(Some(101), "", "\nthread 'main' panicked at src/part_2.rs:9:5:\nattempt to add with overflow\nnote: ...\n")
+            => Status::Panicked("attempt to add with overflow".to_owned()),
+        timed_out: output(None, "", "") => Status::TimedOut,
+        other_part_failed: output(Some(1), "Part 1 error!: bad input\nPart 2: 7\n", "") => Status::Solved("7".to_owned()),
+        other_part_timed_out: output(None, "Part 1: 3\n", "") => Status::TimedOut,
+        silent: output(Some(0), "", "") => Status::Failed("no output".to_owned()),
+    );
+
+    #[test]
+    fn records() {
+        let result = |status| PartResult {
+            year: 2023,
+            day: 6,
+            language: Language::Go,
+            part: 1,
+            status,
+            duration: None,
+            parse_duration: None,
+        };
+
+        assert_eq!(
+            result(Status::Solved("288".to_owned())).record().answer,
+            Ok("288".to_owned())
+        );
+        assert_eq!(
+            result(Status::TimedOut).record().answer,
+            Err("timeout".to_owned())
+        );
+        assert_eq!(
+            result(Status::Panicked("overflow".to_owned()))
+                .record()
+                .language,
+            "go"
+        );
+        assert_eq!(
+            result(Status::Panicked("overflow".to_owned()))
+                .record()
+                .answer,
+            Err("panic: overflow".to_owned())
+        );
+    }
+
+    #[test]
+    fn table() {
+        let result = |day, part, status, millis: Option<u64>| PartResult {
+            year: 2023,
+            day,
+            language: Language::Rust,
+            part,
+            status,
+            duration: millis.map(Duration::from_millis),
+            parse_duration: (day == 1).then(|| Duration::from_micros(350)),
+        };
+
+        let table = summary_table(&[
+            result(1, 1, Status::Solved("54239".to_owned()), Some(2)),
+            result(8, 2, Status::TimedOut, Some(10_000)),
+            result(9, 1, Status::BuildFailed("x".repeat(100)), None),
+        ]);
+
+        assert_eq!(
+            table,
+            [
+                "Year  Day  Language  Part  Status        Time    Parse     Answer",
+                "2023  01   Rust      1     ok            2.00ms  350.00µs  54239",
+                "2023  08   Rust      2     timeout       10.00s  -",
+                &format!(
+                    "2023  09   Rust      1     build failed  -       -         {}...",
+                    "x".repeat(57)
+                ),
+                "",
+                "1/3 parts solved, 1 timeout, 1 build failed",
+            ]
+            .join("\n")
+        );
+    }
+}
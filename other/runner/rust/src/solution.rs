@@ -0,0 +1,277 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::error::Error;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Language {
+    Rust,
+    Go,
+    TypeScript,
+    JavaScript,
+}
+
+impl Language {
+    pub const ALL: [Language; 4] = [
+        Language::Rust,
+        Language::Go,
+        Language::TypeScript,
+        Language::JavaScript,
+    ];
+
+    /// The directory a day's solution in this language lives in
+    pub fn dir_name(self) -> &'static str {
+        match self {
+            Language::Rust => "rust",
+            Language::Go => "go",
+            Language::TypeScript => "ts",
+            Language::JavaScript => "js",
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Language::Rust => "Rust",
+            Language::Go => "Go",
+            Language::TypeScript => "TypeScript",
+            Language::JavaScript => "JavaScript",
+        })
+    }
+}
+
+/// One language's solution for a day, found at `YYYY/DD/<language>`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    pub language: Language,
+    pub dir: PathBuf,
+}
+
+/// A built solution, ready to run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Program {
+    pub executable: PathBuf,
+    pub args: Vec<String>,
+    pub dir: PathBuf,
+    /// Whether the program takes `--part N` to only run one part
+    pub runs_parts: bool,
+}
+
+impl Program {
+    fn new(executable: impl Into<PathBuf>, args: &[&str], dir: &Path) -> Program {
+        Program {
+            executable: executable.into(),
+            args: args.iter().map(|&arg| arg.to_owned()).collect(),
+            dir: dir.to_owned(),
+            runs_parts: false,
+        }
+    }
+
+    /// The command that runs both parts on the day's input
+    pub fn command(&self) -> Command {
+        let mut command = Command::new(&self.executable);
+        command.current_dir(&self.dir).args(&self.args);
+        command
+    }
+
+    /// The command that runs `part` on the day's input, which runs both parts
+    /// if the program can't pick one
+    pub fn part_command(&self, part: u8) -> Command {
+        let mut command = self.command();
+
+        if self.runs_parts {
+            command.args(["--part", &part.to_string()]);
+        }
+
+        command
+    }
+}
+
+impl Solution {
+    /// Builds the solution in release mode, or just works out how to run it
+    /// for languages that build as they run
+    ///
+    /// # Errors
+    /// The compiler output if the build fails
+    pub fn build(&self) -> Result<Program, String> {
+        // The same commands as `advent.sh run`
+        match self.language {
+            Language::Go => Ok(Program::new("go", &["run", "."], &self.dir)),
+            Language::TypeScript | Language::JavaScript => Ok(Program::new(
+                "npm",
+                &["run", "--silent", "start"],
+                &self.dir,
+            )),
+            Language::Rust => {
+                let output = Command::new("cargo")
+                    .args(["build", "--release", "--quiet"])
+                    .current_dir(&self.dir)
+                    .output()
+                    .map_err(|error| format!("couldn't run cargo: {error}"))?;
+
+                if !output.status.success() {
+                    return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+                }
+
+                let name = package_name(&self.dir.join("Cargo.toml"))
+                    .ok_or_else(|| "no package name in Cargo.toml".to_owned())?;
+
+                // `--time` makes days that parse once say how long that took
+                Ok(Program {
+                    runs_parts: true,
+                    ..Program::new(
+                        self.dir.join("target/release").join(name),
+                        &["--time"],
+                        &self.dir,
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// Finds every solution under `root`, optionally only for one year or day,
+/// sorted by year, day and language
+///
+/// # Errors
+/// * `Error::Io` - if a year's directory can't be read
+pub fn discover(root: &Path, year: Option<u32>, day: Option<u32>) -> Result<Vec<Solution>, Error> {
+    let mut solutions = Vec::new();
+
+    for (year_number, year_dir) in numbered_dirs(root)? {
+        if year_number < 2015 || year.is_some_and(|year| year != year_number) {
+            continue;
+        }
+
+        for (day_number, day_dir) in numbered_dirs(&year_dir)? {
+            if !(1..=25).contains(&day_number) || day.is_some_and(|day| day != day_number) {
+                continue;
+            }
+
+            for language in Language::ALL {
+                let dir = day_dir.join(language.dir_name());
+
+                if dir.is_dir() {
+                    solutions.push(Solution {
+                        year: year_number,
+                        day: day_number,
+                        language,
+                        dir,
+                    });
+                }
+            }
+        }
+    }
+
+    solutions.sort_by_key(|solution| (solution.year, solution.day, solution.language));
+
+    Ok(solutions)
+}
+
+/// The subdirectories of `dir` named with a number, like `2023` or `07`
+fn numbered_dirs(dir: &Path) -> Result<Vec<(u32, PathBuf)>, Error> {
+    let io_error = |source| Error::Io {
+        path: dir.to_owned(),
+        source,
+    };
+
+    let mut dirs = Vec::new();
+
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        let number = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse().ok());
+
+        if let (Some(number), true) = (number, path.is_dir()) {
+            dirs.push((number, path));
+        }
+    }
+
+    Ok(dirs)
+}
+
+/// The `name` in a Cargo.toml's `[package]` section
+fn package_name(manifest: &Path) -> Option<String> {
+    let manifest = fs::read_to_string(manifest).ok()?;
+
+    manifest
+        .lines()
+        .skip_while(|line| line.trim() != "[package]")
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+
+            (key.trim() == "name").then(|| value.trim().trim_matches('"').to_owned())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory under the system's temp directory
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc_runner_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn discovers_solutions() {
+        let root = temp_dir("discover");
+
+        for dir in [
+            "2023/01/rust",
+            "2023/02/go",
+            "2023/10/rust",
+            "2022/05/rust",
+            "other/rust",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+
+        let found = |year, day| {
+            discover(&root, year, day)
+                .unwrap()
+                .into_iter()
+                .map(|solution| (solution.year, solution.day))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            found(None, None),
+            [(2022, 5), (2023, 1), (2023, 2), (2023, 10)]
+        );
+        assert_eq!(found(Some(2023), None), [(2023, 1), (2023, 2), (2023, 10)]);
+        assert_eq!(found(Some(2023), Some(10)), [(2023, 10)]);
+        assert_eq!(found(Some(2021), None), []);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn reads_package_name() {
+        let root = temp_dir("manifest");
+        let manifest = root.join("Cargo.toml");
+
+        fs::write(
+            &manifest,
+            "[package]\nname = \"aoc_2023_day_01\"\n\n[dependencies]\nname = \"wrong\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(package_name(&manifest).as_deref(), Some("aoc_2023_day_01"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,405 @@
+use std::{
+    fmt, fs,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    answers::{self, Hint, Rejection},
+    error::Error,
+    http::Client,
+};
+
+/// The file in the repository root holding when the site will take answers
+/// again, as seconds since the Unix epoch
+pub const COOLDOWN_FILE: &str = ".submit_cooldown";
+
+/// What the site said about a submitted answer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        hint: Option<Hint>,
+        /// How long until the site takes another answer
+        wait: Option<Duration>,
+    },
+    /// The answer wasn't checked because the last one was too recent
+    TooSoon(Duration),
+    /// The part was already solved, or isn't unlocked yet
+    WrongLevel,
+    /// A page this doesn't understand, as text
+    Unknown(String),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::Wrong { hint, wait } => {
+                write!(f, "That's not the right answer")?;
+
+                if let Some(hint) = hint {
+                    write!(f, ", it's {hint}")?;
+                }
+
+                if let Some(wait) = wait {
+                    write!(f, "; wait {}s before trying again", wait.as_secs())?;
+                }
+
+                Ok(())
+            }
+            Verdict::TooSoon(wait) => write!(
+                f,
+                "Answered too recently, wait {}s before trying again",
+                wait.as_secs()
+            ),
+            Verdict::WrongLevel => write!(f, "That part is already solved or still locked"),
+            Verdict::Unknown(text) => write!(f, "Unexpected response: {text}"),
+        }
+    }
+}
+
+/// Submits `answer` to one part of a day, unless it's already known to be
+/// wrong or the site wouldn't take it yet, and records what the site said in
+/// the day's answers or rejected answers
+///
+/// # Errors
+/// * `Error::Cooldown` - if the last answer was too recent
+/// * `Error::AlreadySolved` - if the part already has a recorded answer
+/// * `Error::KnownWrong` - if the answer was rejected before, or is past an
+///   answer that was too high or too low
+/// * `Error::Http` - if the request fails
+/// * `Error::Io` or `Error::Write` - if the answers can't be read or written
+pub fn submit(
+    client: &Client,
+    root: &Path,
+    year: u32,
+    day: u32,
+    part: u8,
+    answer: &str,
+    now: SystemTime,
+) -> Result<Verdict, Error> {
+    let day_dir = root.join(year.to_string()).join(format!("{day:02}"));
+    let cooldown = root.join(COOLDOWN_FILE);
+
+    if let Some(remaining) = remaining_cooldown(&cooldown, now) {
+        return Err(Error::Cooldown(remaining));
+    }
+
+    if let Some(solved) = &answers::read(&day_dir)?[usize::from(part - 1)] {
+        return Err(Error::AlreadySolved {
+            answer: solved.clone(),
+        });
+    }
+
+    check_rejected(part, answer, &answers::read_rejected(&day_dir)?)?;
+
+    let path = format!("/{year}/day/{day}/answer");
+    let response = client.post_form(&path, &[("level", &part.to_string()), ("answer", answer)])?;
+
+    if !response.is_success() {
+        return Err(Error::Http {
+            url: client.url(&path),
+            message: format!("status {}", response.status),
+        });
+    }
+
+    let verdict = parse_response(&response.body);
+
+    match &verdict {
+        Verdict::Correct => answers::record(&day_dir, part, answer)?,
+        Verdict::Wrong { hint, wait } => {
+            answers::record_rejected(
+                &day_dir,
+                &Rejection {
+                    part,
+                    answer: answer.to_owned(),
+                    hint: *hint,
+                },
+            )?;
+
+            if let Some(wait) = wait {
+                set_cooldown(&cooldown, now + *wait)?;
+            }
+        }
+        Verdict::TooSoon(wait) => set_cooldown(&cooldown, now + *wait)?,
+        Verdict::WrongLevel | Verdict::Unknown(_) => {}
+    }
+
+    Ok(verdict)
+}
+
+/// Whether an earlier rejection already rules out `answer`
+fn check_rejected(part: u8, answer: &str, rejected: &[Rejection]) -> Result<(), Error> {
+    let known_wrong = |reason| {
+        Err(Error::KnownWrong {
+            answer: answer.to_owned(),
+            reason,
+        })
+    };
+
+    for rejection in rejected.iter().filter(|rejection| rejection.part == part) {
+        if rejection.answer == answer {
+            return known_wrong("it was already rejected".to_owned());
+        }
+
+        let (Ok(number), Ok(rejected), Some(hint)) = (
+            answer.parse::<i128>(),
+            rejection.answer.parse::<i128>(),
+            rejection.hint,
+        ) else {
+            continue;
+        };
+
+        let ruled_out = match hint {
+            Hint::TooHigh => number >= rejected,
+            Hint::TooLow => number <= rejected,
+        };
+
+        if ruled_out {
+            return known_wrong(format!("{rejected} was already {hint}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the verdict out of the page the site answers with
+pub fn parse_response(html: &str) -> Verdict {
+    let text = article_text(html);
+
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+
+        Verdict::Wrong {
+            hint,
+            wait: wait_minutes(&text),
+        }
+    } else if text.contains("You gave an answer too recently") {
+        // If the wait can't be read, a minute is the site's shortest
+        Verdict::TooSoon(time_left(&text).unwrap_or(Duration::from_mins(1)))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(text)
+    }
+}
+
+/// The text of the page's `<article>`, or the whole page if it has none,
+/// without tags and with whitespace collapsed
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map_or(html, |(_, rest)| {
+            rest.split_once("</article>")
+                .map_or(rest, |(article, _)| article)
+        });
+
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The wait in "please wait 5 minutes before trying again"
+fn wait_minutes(text: &str) -> Option<Duration> {
+    let lower = text.to_lowercase();
+    let (_, rest) = lower.split_once("wait ")?;
+    let minutes = match rest.split_whitespace().next()? {
+        "one" => 1,
+        n => n.parse().ok()?,
+    };
+
+    Some(Duration::from_mins(minutes))
+}
+
+/// The wait in "you have 4m 31s left to wait"
+fn time_left(text: &str) -> Option<Duration> {
+    let (before, _) = text.split_once(" left to wait")?;
+    let (_, left) = before.rsplit_once("have ")?;
+
+    let mut seconds = 0;
+
+    for amount in left.split_whitespace() {
+        let (number, unit) = amount.split_at(amount.len() - 1);
+        let number = number.parse::<u64>().ok()?;
+
+        seconds += number
+            * match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+fn remaining_cooldown(path: &Path, now: SystemTime) -> Option<Duration> {
+    let until = fs::read_to_string(path).ok()?.trim().parse().ok()?;
+
+    (UNIX_EPOCH + Duration::from_secs(until))
+        .duration_since(now)
+        .ok()
+        .filter(|remaining| !remaining.is_zero())
+}
+
+fn set_cooldown(path: &Path, until: SystemTime) -> Result<(), Error> {
+    let seconds = until
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+        .ceil();
+
+    fs::write(path, format!("{seconds}\n")).map_err(|source| Error::Write {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::http::stub;
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>")
+    }
+
+    macro_rules! verdict_test {
+        ($suite:ident, $($name:ident: $message:expr => $expected:expr,)*) => {
+            mod $suite {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $name() {
+                        assert_eq!(parse_response(&page($message)), $expected);
+                    }
+                )*
+            }
+        }
+    }
+
+    verdict_test!(verdict_tests,
+        correct: "That's the right answer! You are <span class=\"day-success\">one gold star</span> closer."
+            => Verdict::Correct,
+        too_high: "That's not the right answer; your answer is too high. Please wait one minute before trying again."
+            => Verdict::Wrong { hint: Some(Hint::TooHigh), wait: Some(Duration::from_mins(1)) },
+        too_low: "That's not the right answer; your answer is too low. Please wait 5 minutes before trying again."
+            => Verdict::Wrong { hint: Some(Hint::TooLow), wait: Some(Duration::from_mins(5)) },
+        no_hint: "That's not the right answer. If you're stuck, make sure you're using the full input data."
+            => Verdict::Wrong { hint: None, wait: None },
+        too_soon: "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 31s left to wait."
+            => Verdict::TooSoon(Duration::from_secs(271)),
+        seconds_left: "You gave an answer too recently; you have 31s left to wait."
+            => Verdict::TooSoon(Duration::from_secs(31)),
+        wrong_level: "You don't seem to be solving the right level.  Did you already complete it?"
+            => Verdict::WrongLevel,
+        unknown: "Something <em>else</em>" => Verdict::Unknown("Something else".to_owned()),
+    );
+
+    /// A fresh repository root under the system's temp directory
+    fn temp_root(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc_runner_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("2023/01")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn records_what_the_site_says() {
+        let root = temp_root("submit");
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let (base_url, server) = stub::serve(vec![
+            (200, page("That's not the right answer; your answer is too high. Please wait one minute before trying again.")),
+            (200, page("That's the right answer!")),
+        ]);
+        let client = Client::new(&base_url, "abc");
+        let submit = |answer, now| submit(&client, &root, 2023, 1, 1, answer, now);
+
+        assert_eq!(
+            submit("100", now).unwrap(),
+            Verdict::Wrong {
+                hint: Some(Hint::TooHigh),
+                wait: Some(Duration::from_mins(1))
+            }
+        );
+
+        // None of these reach the server
+        assert!(matches!(
+            submit("50", now + Duration::from_secs(30)),
+            Err(Error::Cooldown(remaining)) if remaining == Duration::from_secs(30)
+        ));
+
+        let later = now + Duration::from_mins(1);
+
+        assert_eq!(
+            submit("100", later).unwrap_err().to_string(),
+            "100 is known to be wrong: it was already rejected"
+        );
+        assert_eq!(
+            submit("150", later).unwrap_err().to_string(),
+            "150 is known to be wrong: 100 was already too high"
+        );
+
+        assert_eq!(submit("54", later).unwrap(), Verdict::Correct);
+        assert_eq!(
+            submit("54", later).unwrap_err().to_string(),
+            "part already solved with 54"
+        );
+
+        let requests = server.join().unwrap();
+
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("POST /2023/day/1/answer "));
+        assert!(requests[1].ends_with("level=1&answer=54"));
+        assert_eq!(
+            fs::read_to_string(root.join("2023/01/rejected.txt")).unwrap(),
+            "Part 1: 100 (too high)\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("2023/01/answers.txt")).unwrap(),
+            "Part 1: 54\n"
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn reports_failed_requests() {
+        let root = temp_root("submit_failed");
+        let (base_url, server) = stub::serve(vec![(500, "Oops".to_owned())]);
+        let client = Client::new(&base_url, "abc");
+
+        assert!(matches!(
+            submit(&client, &root, 2023, 1, 2, "7", UNIX_EPOCH),
+            Err(Error::Http { url, message })
+                if url == format!("{base_url}/2023/day/1/answer") && message == "status 500"
+        ));
+
+        server.join().unwrap();
+        fs::remove_dir_all(root).unwrap();
+    }
+}
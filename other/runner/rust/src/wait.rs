@@ -0,0 +1,383 @@
+use std::{
+    fs, iter,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aoc_helpers::eastern;
+
+use crate::{error::Error, http::Client};
+
+/// Where a new day's Rust solution is copied from
+pub const TEMPLATE_DIR: &str = "other/templates/rust";
+
+/// Build output and lockfiles in the template that a new day shouldn't get
+const SKIPPED: [&str; 2] = ["target", "Cargo.lock"];
+
+/// Tells the time and waits, so tests can do both instantly
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&mut self, duration: Duration);
+}
+
+/// The real time
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// How many times to try downloading an input, and how long to wait between
+/// tries. The wait doubles after each one, up to `max_delay`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Backoff {
+    pub attempts: u32,
+    pub first_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Backoff {
+        Backoff {
+            attempts: 6,
+            first_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Backoff {
+    /// The wait before each retry
+    fn delays(&self) -> impl Iterator<Item = Duration> + '_ {
+        iter::successors(Some(self.first_delay), |delay| {
+            Some((*delay * 2).min(self.max_delay))
+        })
+        .take(self.attempts.saturating_sub(1) as usize)
+    }
+}
+
+/// When `day`'s puzzle in `year` unlocks, at midnight in New York
+pub fn unlock_time(year: u32, day: u32) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(eastern::unlock_timestamp(year, day))
+}
+
+/// Waits until `unlock`, calling `tick` with the whole seconds left as each
+/// one passes and with 0 at the end. Returns whether it had to wait at all.
+pub fn count_down(clock: &mut impl Clock, unlock: SystemTime, mut tick: impl FnMut(u64)) -> bool {
+    let mut waited = false;
+
+    while let Ok(remaining) = unlock.duration_since(clock.now()) {
+        if remaining.is_zero() {
+            break;
+        }
+
+        let fraction = remaining.subsec_nanos();
+        tick(remaining.as_secs() + u64::from(fraction > 0));
+        waited = true;
+
+        // Wake on the second, so the last sleep ends right at `unlock`
+        clock.sleep(if fraction > 0 {
+            Duration::from_nanos(fraction.into())
+        } else {
+            Duration::from_secs(1)
+        });
+    }
+
+    if waited {
+        tick(0);
+    }
+
+    waited
+}
+
+/// Copies the Rust template to `YYYY/DD/rust` under `root`, with the crate and
+/// README named for the day. Returns `None` if the day already has one.
+///
+/// # Errors
+/// * `Error::Io` or `Error::Write` - if the template can't be copied
+pub fn scaffold(root: &Path, year: u32, day: u32) -> Result<Option<PathBuf>, Error> {
+    let dir = root
+        .join(year.to_string())
+        .join(format!("{day:02}"))
+        .join("rust");
+
+    if dir.exists() {
+        return Ok(None);
+    }
+
+    let fill_in = |text: &str| {
+        text.replace("aoc_YYYY_day_DD", &format!("aoc_{year}_day_{day:02}"))
+            .replace("YYYY Day D", &format!("{year} Day {day}"))
+    };
+
+    copy_dir(&root.join(TEMPLATE_DIR), &dir, &fill_in)?;
+
+    Ok(Some(dir))
+}
+
+fn copy_dir(from: &Path, to: &Path, fill_in: &dyn Fn(&str) -> String) -> Result<(), Error> {
+    let read_error = |path: &Path| {
+        let path = path.to_owned();
+        move |source| Error::Io { path, source }
+    };
+    let write_error = |path: &Path| {
+        let path = path.to_owned();
+        move |source| Error::Write { path, source }
+    };
+
+    fs::create_dir_all(to).map_err(write_error(to))?;
+
+    for entry in fs::read_dir(from).map_err(read_error(from))? {
+        let entry = entry.map_err(read_error(from))?;
+        let name = entry.file_name();
+
+        if SKIPPED.iter().any(|&skipped| name == skipped) {
+            continue;
+        }
+
+        let (source, target) = (entry.path(), to.join(&name));
+
+        if source.is_dir() {
+            copy_dir(&source, &target, fill_in)?;
+        } else {
+            let contents = fs::read(&source).map_err(read_error(&source))?;
+            let contents = match String::from_utf8(contents) {
+                Ok(text) => fill_in(&text).into_bytes(),
+                Err(error) => error.into_bytes(),
+            };
+
+            fs::write(&target, contents).map_err(write_error(&target))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Downloads a day's input to `YYYY/DD/input.txt` under `root`, unless it's
+/// already there. The site can take a moment to serve inputs after they
+/// unlock, so failed requests are retried according to `backoff`.
+///
+/// # Errors
+/// * `Error::Http` - if the last try fails
+/// * `Error::Io` or `Error::Write` - if the input can't be read or written
+pub fn fetch_input(
+    client: &Client,
+    root: &Path,
+    year: u32,
+    day: u32,
+    backoff: &Backoff,
+    clock: &mut impl Clock,
+) -> Result<PathBuf, Error> {
+    let path = root
+        .join(year.to_string())
+        .join(format!("{day:02}"))
+        .join("input.txt");
+    let mut delays = backoff.delays();
+
+    loop {
+        match client.get_cached(&format!("/{year}/day/{day}/input"), &path, false) {
+            Ok(_) => return Ok(path),
+            Err(error @ Error::Http { .. }) => match delays.next() {
+                Some(delay) => clock.sleep(delay),
+                None => return Err(error),
+            },
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::stub;
+
+    /// Moves forward only when slept on
+    struct FakeClock {
+        now: SystemTime,
+        sleeps: Vec<Duration>,
+    }
+
+    impl FakeClock {
+        fn new(now: SystemTime) -> FakeClock {
+            FakeClock {
+                now,
+                sleeps: Vec::new(),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.now
+        }
+
+        fn sleep(&mut self, duration: Duration) {
+            self.now += duration;
+            self.sleeps.push(duration);
+        }
+    }
+
+    #[test]
+    fn counts_down_to_unlock() {
+        let unlock = unlock_time(2023, 1);
+        let mut clock = FakeClock::new(unlock - Duration::from_millis(2500));
+        let mut ticks = Vec::new();
+
+        assert!(count_down(&mut clock, unlock, |seconds| ticks.push(seconds)));
+        assert_eq!(ticks, [3, 2, 1, 0]);
+        assert_eq!(
+            clock.sleeps,
+            [
+                Duration::from_millis(500),
+                Duration::from_secs(1),
+                Duration::from_secs(1)
+            ]
+        );
+        assert_eq!(clock.now, unlock);
+    }
+
+    #[test]
+    fn already_unlocked() {
+        let unlock = unlock_time(2023, 1);
+        let mut clock = FakeClock::new(unlock + Duration::from_secs(1));
+
+        assert!(!count_down(&mut clock, unlock, |_| panic!("ticked")));
+        assert!(!count_down(&mut FakeClock::new(unlock), unlock, |_| {
+            panic!("ticked")
+        }));
+    }
+
+    #[test]
+    fn backs_off() {
+        let backoff = Backoff {
+            attempts: 5,
+            first_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+        };
+
+        assert_eq!(
+            backoff
+                .delays()
+                .map(|delay| delay.as_secs())
+                .collect::<Vec<_>>(),
+            [1, 2, 4, 5]
+        );
+    }
+
+    #[test]
+    fn scaffolds_from_template() {
+        let root = std::env::temp_dir().join(format!("aoc_runner_wait_{}", std::process::id()));
+        let template = root.join(TEMPLATE_DIR);
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(template.join("src")).unwrap();
+        fs::create_dir_all(template.join("target/debug")).unwrap();
+        fs::write(template.join("Cargo.toml"), "name = \"aoc_YYYY_day_DD\"\n").unwrap();
+        fs::write(template.join("Cargo.lock"), "").unwrap();
+        fs::write(template.join("README.md"), "# YYYY Day D - Rust\n").unwrap();
+        fs::write(template.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+        let dir = root.join("2023/05/rust");
+        assert_eq!(scaffold(&root, 2023, 5).unwrap(), Some(dir.clone()));
+
+        assert_eq!(
+            fs::read_to_string(dir.join("Cargo.toml")).unwrap(),
+            "name = \"aoc_2023_day_05\"\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("README.md")).unwrap(),
+            "# 2023 Day 5 - Rust\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("src/main.rs")).unwrap(),
+            "fn main() {}\n"
+        );
+        assert!(!dir.join("target").exists());
+        assert!(!dir.join("Cargo.lock").exists());
+
+        // Never overwrite a solution that's already there
+        assert_eq!(scaffold(&root, 2023, 5).unwrap(), None);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn retries_input_downloads() {
+        let root = std::env::temp_dir().join(format!("aoc_runner_input_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (base_url, server) = stub::serve(vec![
+            (
+                404,
+                "Please don't repeatedly request this endpoint".to_owned(),
+            ),
+            (502, "Bad gateway".to_owned()),
+            (200, "1 2 3\n".to_owned()),
+        ]);
+        let backoff = Backoff {
+            attempts: 4,
+            first_delay: Duration::from_secs(1),
+            max_delay: Duration::from_millis(1500),
+        };
+        let mut clock = FakeClock::new(UNIX_EPOCH);
+
+        let path = fetch_input(
+            &Client::new(&base_url, "abc"),
+            &root,
+            2023,
+            1,
+            &backoff,
+            &mut clock,
+        )
+        .unwrap();
+
+        assert_eq!(path, root.join("2023/01/input.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2 3\n");
+        assert_eq!(
+            clock.sleeps,
+            [Duration::from_secs(1), Duration::from_millis(1500)]
+        );
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[2].starts_with("GET /2023/day/1/input HTTP/1.1\r\n"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn gives_up_on_input_downloads() {
+        let root = std::env::temp_dir().join(format!("aoc_runner_no_input_{}", std::process::id()));
+        let (base_url, server) = stub::serve(vec![
+            (404, "Not yet".to_owned()),
+            (404, "Not yet".to_owned()),
+        ]);
+        let backoff = Backoff {
+            attempts: 2,
+            ..Backoff::default()
+        };
+        let mut clock = FakeClock::new(UNIX_EPOCH);
+
+        assert!(matches!(
+            fetch_input(
+                &Client::new(&base_url, "abc"),
+                &root,
+                2023,
+                1,
+                &backoff,
+                &mut clock,
+            ),
+            Err(Error::Http { message, .. }) if message == "status 404"
+        ));
+        assert_eq!(clock.sleeps, [Duration::from_secs(1)]);
+        assert!(!root.join("2023/01/input.txt").exists());
+
+        server.join().unwrap();
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -1,3 +1,4 @@
+use aoc_helpers::context::Locate;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -8,3 +9,5 @@ pub enum Error {
     #[error("unimplemented")]
     Unimplemented,
 }
+
+impl Locate for Error {}
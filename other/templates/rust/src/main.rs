@@ -1,15 +1,12 @@
 // #![deny(clippy::all, clippy::pedantic)]
-use aoc_helpers::answer::AocAnswer;
-use std::fs;
+use aoc_helpers::cli;
+use std::process::ExitCode;
 
 mod error;
 mod part_1;
 mod part_2;
 mod shared;
 
-fn main() {
-    let input = fs::read_to_string("../input.txt").unwrap();
-
-    part_1::run(&input).print_aoc_answer(1);
-    part_2::run(&input).print_aoc_answer(2);
+fn main() -> ExitCode {
+    cli::Runner::from_env().solve(shared::parse, part_1::run, part_2::run)
 }
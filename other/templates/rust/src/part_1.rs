@@ -1,18 +1,19 @@
-use crate::error::Error;
+use crate::{error::Error, shared::Parsed};
 
-pub fn run(input: &str) -> Result<String, Error> {
+pub fn run(_parsed: &Parsed) -> Result<String, Error> {
     Err(Error::Unimplemented)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shared::parse;
 
     #[test]
     fn example() {
         let input = r"";
 
-        let output = run(input);
+        let output = parse(input).and_then(|parsed| run(&parsed));
 
         assert_eq!(output, Ok("".to_owned()));
     }
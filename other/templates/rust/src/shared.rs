@@ -1 +1,8 @@
+use crate::error::Error;
 
+/// The puzzle input, parsed once and shared by both parts
+pub struct Parsed {}
+
+pub fn parse(_input: &str) -> Result<Parsed, Error> {
+    Err(Error::Unimplemented)
+}